@@ -1,4 +1,4 @@
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Attr {
     _brace_token: syn::token::Brace,
     fields: syn::punctuated::Punctuated<AttrField, syn::Token![,]>,
@@ -11,6 +11,32 @@ impl Attr {
             .find(|field| field.ident.to_string().as_str() == ident)
             .map(|field| syn::parse2(field.expr.clone()))
     }
+
+    pub fn find<T: syn::parse::Parse>(&self, ident: &str) -> syn::Result<Option<T>> {
+        self.find_field(ident).transpose()
+    }
+
+    /// Returns the first key of `keys` that is set.
+    pub fn find_key(&self, keys: &[&str]) -> Option<&syn::Ident> {
+        self.fields
+            .iter()
+            .map(|field| &field.ident)
+            .find(|ident| keys.iter().any(|key| *ident == key))
+    }
+
+    /// Appends the fields of `base`. Values of `self` take precedence because they are found first.
+    pub fn merge(mut self, base: Attr) -> Attr {
+        self.fields.extend(base.fields);
+        self
+    }
+
+    pub fn find_bool(&self, ident: &str) -> syn::Result<bool> {
+        match self.find_field::<syn::LitBool>(ident) {
+            Some(Ok(syn::LitBool { value, span: _ })) => Ok(value),
+            Some(Err(e)) => Err(e),
+            None => Ok(false),
+        }
+    }
 }
 
 impl syn::parse::Parse for Attr {
@@ -28,7 +54,7 @@ impl syn::parse::Parse for Attr {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct AttrField {
     ident: syn::Ident,
     _colon_token: syn::Token![:],
@@ -40,7 +66,34 @@ impl syn::parse::Parse for AttrField {
         Ok(Self {
             ident: input.parse()?,
             _colon_token: input.parse()?,
-            expr: input.parse()?,
+            expr: parse_value(input)?,
         })
     }
 }
+
+/// Collects the tokens of a value up to the next `,` that is not part of a generic argument list.
+fn parse_value(input: syn::parse::ParseStream) -> syn::Result<proc_macro2::TokenStream> {
+    input.step(|cursor| {
+        let mut tokens = proc_macro2::TokenStream::new();
+        let mut rest = *cursor;
+        let mut angle_depth = 0usize;
+        let mut previous_arrow_start = false;
+        while let Some((tt, next)) = rest.token_tree() {
+            if let proc_macro2::TokenTree::Punct(punct) = &tt {
+                match punct.as_char() {
+                    ',' if angle_depth == 0 => break,
+                    '<' => angle_depth += 1,
+                    '>' if !previous_arrow_start => angle_depth = angle_depth.saturating_sub(1),
+                    _ => {}
+                }
+                previous_arrow_start = punct.as_char() == '-'
+                    && punct.spacing() == proc_macro2::Spacing::Joint;
+            } else {
+                previous_arrow_start = false;
+            }
+            tokens.extend([tt]);
+            rest = next;
+        }
+        Ok((tokens, rest))
+    })
+}
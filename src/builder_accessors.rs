@@ -0,0 +1,126 @@
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+
+use crate::{
+    data::{DeriveData, Field},
+    syn_attribute_helper::construct_doc_comment,
+};
+
+/// Builds the getters, the functions that unset or reset values and `reset`.
+pub fn build_accessor_functions(data: &DeriveData) -> TokenStream {
+    let methods = builder_method_names(data);
+    let fields = data.fields.as_ref().iter().filter(|f| !f.skip);
+    let accessors = fields.map(|f| build_field_accessors(f, &methods));
+    let reset_comment = construct_doc_comment(
+        format!("Unset all values of the {}.", data.builder_ident).as_str(),
+    );
+    quote::quote!(
+        #(#accessors)*
+        #reset_comment
+        pub fn reset(&mut self) {
+            *self = Self::default();
+        }
+    )
+}
+
+/// The names of all other functions of the builder. A getter with one of these names is not
+/// generated, for example if a setter has no prefix or a field is called `build`.
+fn builder_method_names(data: &DeriveData) -> Vec<String> {
+    let (try_build, build) = data.build_fn_idents();
+    let mut names = [
+        "default",
+        "reset",
+        "merge",
+        "fill_missing_from",
+        "is_set",
+        "fields_set",
+        "missing_fields",
+        "from_env",
+        "from_env_source",
+        "apply_env",
+        "apply_env_source",
+        "set_by_name",
+        "from_pairs",
+    ]
+    .into_iter()
+    .map(String::from)
+    .chain([try_build.to_string(), build.to_string()])
+    .chain(data.constructor.as_ref().map(ToString::to_string))
+    .collect::<Vec<_>>();
+    data.fields.as_ref().iter().filter(|f| !f.skip).for_each(|f| {
+        let setters = [&f.setters.owned, &f.setters.mutable].into_iter().flatten();
+        names.extend(setters.clone().map(ToString::to_string));
+        if f.default.is_some() {
+            names.extend(setters.map(|setter| format!("{setter}_default")));
+        }
+        names.push(format!("clear_{}", f.name()));
+        if let Some(each) = &f.each {
+            names.extend(
+                [&each.setters.owned, &each.setters.mutable]
+                    .into_iter()
+                    .flatten()
+                    .map(ToString::to_string),
+            );
+            names.push(format!("extend_{}", f.name()));
+        }
+    });
+    names
+}
+
+fn build_field_accessors(field: &Field, methods: &[String]) -> TokenStream {
+    let ident = &field.ident;
+    let ty = field.builder_ty();
+    let getter = (!methods.contains(&ident.to_string()))
+        .then(|| {
+            let comment =
+                construct_doc_comment(format!("Returns the {ident} if it is set.").as_str());
+            quote::quote!(
+                #comment
+                pub fn #ident(&self) -> Option<&#ty> {
+                    self.#ident.as_ref()
+                }
+            )
+        });
+    let clear_ident = syn::Ident::new(
+        format!("clear_{}", ident.unraw()).as_str(),
+        proc_macro2::Span::call_site(),
+    );
+    let clear_comment = construct_doc_comment(format!("Unset the {ident}.").as_str());
+    let default_setters = field.default.as_ref().map(|default| {
+        let comment = construct_doc_comment(
+            format!("Set the {ident} to its default value `{}`.", quote::quote!(#default)).as_str(),
+        );
+        let owned = field.setters.owned.as_ref().map(|owned| {
+            let fn_ident = syn::Ident::new(format!("{owned}_default").as_str(), owned.span());
+            quote::quote!(
+                #comment
+                #[must_use]
+                pub fn #fn_ident(mut self) -> Self {
+                    self.#ident = Some(#default);
+                    self
+                }
+            )
+        });
+        let mutable = field.setters.mutable.as_ref().map(|mutable| {
+            let fn_ident = syn::Ident::new(format!("{mutable}_default").as_str(), mutable.span());
+            quote::quote!(
+                #comment
+                pub fn #fn_ident(&mut self) {
+                    self.#ident = Some(#default);
+                }
+            )
+        });
+        quote::quote!(
+            #owned
+            #mutable
+        )
+    });
+    quote::quote!(
+        #getter
+        #clear_comment
+        pub fn #clear_ident(&mut self) {
+            self.#ident = None;
+        }
+        #default_setters
+    )
+}
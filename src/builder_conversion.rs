@@ -0,0 +1,99 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    builder_struct::{clone_bounds, split_generics},
+    data::{DeriveData, Field},
+};
+
+/// Builds `From<Struct>` for the builder, `Struct::into_builder` and `Struct::to_builder`.
+/// The functions of the struct can be renamed or disabled with `into_builder` and `to_builder`.
+/// The returned builders have all values of the struct set.
+/// Returns `None` for enum variants and functions, which can not be turned back into a builder.
+pub fn build_conversions(data: &DeriveData) -> Option<TokenStream> {
+    if data.variant.is_some() || data.function.is_some() {
+        return None;
+    }
+    let DeriveData {
+        struct_ident,
+        builder_ident,
+        fields,
+        ..
+    } = data;
+    let (generics, generics_without_bounds, where_clause) = split_generics(&data.generics);
+    let fields = fields.as_ref().iter().filter(|f| !f.skip).collect::<Vec<_>>();
+    // The typestate builder has the types of the fields as states once all are set.
+    let states = fields
+        .iter()
+        .filter(|f| data.typestate && f.is_required())
+        .map(|f| {
+            let ty = &f.ty;
+            quote::quote!(#ty)
+        });
+    let builder_args = generics_without_bounds
+        .params
+        .iter()
+        .map(|p| quote::quote!(#p))
+        .chain(states)
+        .collect::<Vec<_>>();
+    let builder_type = quote::quote!(#builder_ident<#(#builder_args),*>);
+    let builder_fields = |value: &dyn Fn(&Field) -> TokenStream| {
+        let fields = fields.iter().map(|f| {
+            let ident = &f.ident;
+            let value = value(f);
+            if (data.typestate && f.is_required()) || f.is_optional.is_some() {
+                quote::quote!(#ident: #value,)
+            } else {
+                quote::quote!(#ident: Some(#value),)
+            }
+        });
+        quote::quote!(
+            #builder_ident {
+                #(#fields)*
+                __marker: std::marker::PhantomData,
+            }
+        )
+    };
+    let from_value = builder_fields(&|f| {
+        let member = &f.member;
+        quote::quote!(value.#member)
+    });
+    let from_ref = builder_fields(&|f| {
+        let member = &f.member;
+        let ty = &f.ty;
+        quote::quote!(<#ty as Clone>::clone(&self.#member))
+    });
+    let clone_bounds = clone_bounds(&fields);
+    let clone_where_clause = match &where_clause {
+        Some(where_clause) => quote::quote!(#where_clause #clone_bounds),
+        None => quote::quote!(where #clone_bounds),
+    };
+    let into_builder = data.into_builder_fn.as_ref().map(|into_builder| {
+        quote::quote!(
+            /// Create a builder with all values of this struct set.
+            pub fn #into_builder(self) -> #builder_type {
+                <#builder_type>::from(self)
+            }
+        )
+    });
+    let to_builder = data.to_builder_fn.as_ref().map(|to_builder| {
+        quote::quote!(
+            /// Create a builder with copies of all values of this struct set.
+            pub fn #to_builder(&self) -> #builder_type
+            #clone_where_clause
+            {
+                #from_ref
+            }
+        )
+    });
+    Some(quote::quote!(
+        impl #generics From<#struct_ident #generics_without_bounds> for #builder_type #where_clause {
+            fn from(value: #struct_ident #generics_without_bounds) -> Self {
+                #from_value
+            }
+        }
+        impl #generics #struct_ident #generics_without_bounds #where_clause {
+            #into_builder
+            #to_builder
+        }
+    ))
+}
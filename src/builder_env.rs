@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    builder_error_enum::field_ident_to_parse_variant_ident, data::DeriveData,
+    syn_attribute_helper::construct_doc_comment,
+};
+
+/// Builds the functions that set the values of a builder from environment variables.
+/// Returns `None` if no field is read from an environment variable.
+pub fn build_env_functions(data: &DeriveData) -> Option<TokenStream> {
+    let DeriveData {
+        builder_ident,
+        error_ident,
+        fields,
+        ..
+    } = data;
+    let env_fields = fields
+        .as_ref()
+        .iter()
+        .filter_map(|f| Some((f, f.env.as_ref()?)))
+        .map(|(f, env)| {
+            let ident = &f.ident;
+            let ty = f.builder_ty();
+            let variant = field_ident_to_parse_variant_ident(ident);
+            quote::quote!(
+                if let Some(value) = source(#env) {
+                    match <#ty as std::str::FromStr>::from_str(value.as_str()) {
+                        Ok(value) => self.#ident = Some(value),
+                        Err(e) => return Err(#error_ident::#variant(e.to_string())),
+                    }
+                }
+            )
+        })
+        .collect::<Vec<_>>();
+    if env_fields.is_empty() {
+        return None;
+    }
+    let variables = fields
+        .as_ref()
+        .iter()
+        .filter_map(|f| Some(format!("* {}: {}\n", f.env.as_ref()?, f.ident)))
+        .collect::<String>();
+    let comments = [
+        construct_doc_comment("# Environment variables"),
+        construct_doc_comment(variables.as_str()),
+    ];
+    let from_env_comment = construct_doc_comment(
+        format!("Create a new {builder_ident} with the values of the environment variables.").as_str(),
+    );
+    let apply_env_comment =
+        construct_doc_comment("Set the values of all environment variables that are set.");
+    let source_comment = construct_doc_comment(
+        "The variables are read from `source`, which returns the value of a variable or `None` if it is not set.",
+    );
+    Some(quote::quote!(
+        #from_env_comment
+        #(#comments)*
+        pub fn from_env() -> Result<Self, #error_ident> {
+            Self::default().apply_env()
+        }
+        #from_env_comment
+        #source_comment
+        #(#comments)*
+        pub fn from_env_source<F>(source: F) -> Result<Self, #error_ident>
+        where
+            F: Fn(&str) -> Option<String>,
+        {
+            Self::default().apply_env_source(source)
+        }
+        #apply_env_comment
+        #(#comments)*
+        pub fn apply_env(self) -> Result<Self, #error_ident> {
+            self.apply_env_source(|key| std::env::var(key).ok())
+        }
+        #apply_env_comment
+        #source_comment
+        #(#comments)*
+        pub fn apply_env_source<F>(mut self, source: F) -> Result<Self, #error_ident>
+        where
+            F: Fn(&str) -> Option<String>,
+        {
+            #(#env_fields)*
+            Ok(self)
+        }
+    ))
+}
@@ -1,9 +1,21 @@
-use crate::{data::Field, syn_attribute_helper::construct_attribute};
+use syn::ext::IdentExt;
 
-pub fn build_error(fields: &[Field], error_ident: &syn::Ident) -> syn::ItemEnum {
-    let variants = fields
-        .into_iter()
-        .filter(|f| f.default.is_none() && f.is_optional.is_none())
+use crate::{
+    data::{DeriveData, Validator},
+    syn_attribute_helper::{construct_attribute, construct_doc_comment},
+};
+
+pub fn build_error(data: &DeriveData) -> syn::ItemEnum {
+    let DeriveData {
+        error_ident,
+        collect_missing,
+        fields,
+        ..
+    } = data;
+    let mut variants: syn::punctuated::Punctuated<syn::Variant, syn::Token![,]> = fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.is_required())
         .map(|f| {
             let ident = field_ident_to_error_variant_ident(&f.ident);
             syn::Variant {
@@ -14,8 +26,64 @@ pub fn build_error(fields: &[Field], error_ident: &syn::Ident) -> syn::ItemEnum
             }
         })
         .collect();
+    fields
+        .as_ref()
+        .iter()
+        .chain(data.required_args.as_ref())
+        .filter_map(|f| Some((&f.ident, f.validate.as_ref()?)))
+        .for_each(|(ident, validator)| {
+            let error = &validator.error;
+            variants.push(syn::Variant {
+                attrs: vec![construct_doc_comment(
+                    format!("The validation of {ident} failed.").as_str(),
+                )],
+                discriminant: Default::default(),
+                fields: syn::Fields::Unnamed(syn::parse_quote!((#error))),
+                ident: field_ident_to_invalid_variant_ident(ident),
+            });
+        });
+    fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.env.is_some())
+        .for_each(|f| {
+            let ident = &f.ident;
+            variants.push(syn::Variant {
+                attrs: vec![construct_doc_comment(
+                    format!("The environment variable of {ident} could not be parsed.").as_str(),
+                )],
+                discriminant: Default::default(),
+                fields: syn::Fields::Unnamed(syn::parse_quote!((String))),
+                ident: field_ident_to_parse_variant_ident(ident),
+            });
+        });
+    if let Some(Validator { error, .. }) = &data.validate {
+        variants.push(syn::Variant {
+            attrs: vec![construct_doc_comment(
+                format!("The validation of the constructed {} failed.", data.struct_ident).as_str(),
+            )],
+            discriminant: Default::default(),
+            fields: syn::Fields::Unnamed(syn::parse_quote!((#error))),
+            ident: syn::Ident::new("Validation", proc_macro2::Span::call_site()),
+        });
+    }
+    if *collect_missing {
+        variants.push(syn::Variant {
+            attrs: vec![construct_doc_comment("All required values that have not been set.")],
+            discriminant: Default::default(),
+            fields: syn::Fields::Unnamed(syn::parse_quote!((Vec<&'static str>))),
+            ident: syn::Ident::new("Missing", proc_macro2::Span::call_site()),
+        });
+    }
+    // Validation errors only have to implement Debug and Display, PartialEq and Eq are implemented
+    // in build_error_impl with bounds on the error types instead.
+    let derives: &[&str] = if has_validation_errors(data) {
+        &["Debug"]
+    } else {
+        &["Debug", "PartialEq", "Eq"]
+    };
     syn::ItemEnum {
-        attrs: vec![construct_attribute("derive", &["Debug", "PartialEq", "Eq"])],
+        attrs: vec![construct_attribute("derive", derives)],
         vis: syn::Visibility::Public(syn::VisPublic {
             pub_token: Default::default(),
         }),
@@ -27,10 +95,17 @@ pub fn build_error(fields: &[Field], error_ident: &syn::Ident) -> syn::ItemEnum
     }
 }
 
-pub fn build_error_impl(fields: &[Field], error_ident: &syn::Ident) -> proc_macro2::TokenStream {
+pub fn build_error_impl(data: &DeriveData) -> proc_macro2::TokenStream {
+    let DeriveData {
+        error_ident,
+        collect_missing,
+        fields,
+        ..
+    } = data;
     let arms = fields
-        .into_iter()
-        .filter(|f| f.default.is_none() && f.is_optional.is_none())
+        .as_ref()
+        .iter()
+        .filter(|f| f.is_required())
         .map(|f| {
             let variant = field_ident_to_error_variant_ident(&f.ident);
             let field_ident = &f.ident;
@@ -38,12 +113,79 @@ pub fn build_error_impl(fields: &[Field], error_ident: &syn::Ident) -> proc_macr
                 Self::#variant => write!(f, stringify!(Error #field_ident not set)),
             )
         });
+    let invalid_arms = fields
+        .as_ref()
+        .iter()
+        .chain(data.required_args.as_ref())
+        .filter(|f| f.validate.is_some())
+        .map(|f| {
+            let variant = field_ident_to_invalid_variant_ident(&f.ident);
+            let message = format!("Error {} invalid: {{}}", f.ident);
+            quote::quote!(
+                Self::#variant(e) => write!(f, #message, e),
+            )
+        });
+    let parse_arms = fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.env.is_some())
+        .map(|f| {
+            let variant = field_ident_to_parse_variant_ident(&f.ident);
+            let message = format!("Error {} could not be parsed: {{}}", f.ident);
+            quote::quote!(
+                Self::#variant(e) => write!(f, #message, e),
+            )
+        });
+    let validation_arm = data.validate.as_ref().map(|_| {
+        let message = format!("Error {} invalid: {{}}", data.struct_ident);
+        quote::quote!(
+            Self::Validation(e) => write!(f, #message, e),
+        )
+    });
+    let missing_fields_arms = fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.is_required())
+        .map(|f| {
+            let variant = field_ident_to_error_variant_ident(&f.ident);
+            let name = f.name();
+            quote::quote!(
+                Self::#variant => vec![#name],
+            )
+        });
+    let (missing_arm, missing_fields_arm) = if *collect_missing {
+        (
+            quote::quote!(Self::Missing(fields) => write!(f, "Error {} not set", fields.join(", ")),),
+            quote::quote!(Self::Missing(fields) => fields.clone(),),
+        )
+    } else {
+        Default::default()
+    };
+
+    let eq_impl = has_validation_errors(data).then(|| build_eq_impl(data));
 
     quote::quote!(
+        #eq_impl
+        impl #error_ident {
+            /// Returns the names of all required values that have not been set.
+            pub fn missing_fields(&self) -> Vec<&'static str> {
+                match self {
+                    #(#missing_fields_arms)*
+                    #missing_fields_arm
+                    #[allow(unreachable_patterns)]
+                    _ => Vec::new()
+                }
+            }
+        }
         impl std::fmt::Display for #error_ident {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 match self {
                     #(#arms)*
+                    #(#invalid_arms)*
+                    #(#parse_arms)*
+                    #validation_arm
+                    #missing_arm
+                    #[allow(unreachable_patterns)]
                     _ => Ok(())
                 }
             }
@@ -52,14 +194,72 @@ pub fn build_error_impl(fields: &[Field], error_ident: &syn::Ident) -> proc_macr
     )
 }
 
+fn has_validation_errors(data: &DeriveData) -> bool {
+    data.validate.is_some()
+        || data
+            .fields
+            .as_ref()
+            .iter()
+            .chain(data.required_args.as_ref())
+            .any(|f| f.validate.is_some())
+}
+
+/// Builds `PartialEq` and `Eq` for an error with validation errors.
+/// The bounds are higher ranked, so the impls are only checked where they are used.
+fn build_eq_impl(data: &DeriveData) -> proc_macro2::TokenStream {
+    let error_ident = &data.error_ident;
+    let error_enum = build_error(data);
+    let arms = error_enum
+        .variants
+        .iter()
+        .filter(|v| matches!(v.fields, syn::Fields::Unnamed(_)))
+        .map(|v| {
+            let ident = &v.ident;
+            quote::quote!((Self::#ident(a), Self::#ident(b)) => a == b,)
+        });
+    let error_types = error_enum
+        .variants
+        .iter()
+        .filter_map(|v| match &v.fields {
+            syn::Fields::Unnamed(fields) => fields.unnamed.first().map(|f| &f.ty),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    quote::quote!(
+        impl PartialEq for #error_ident where #(for<'__a> #error_types: PartialEq,)* {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    #(#arms)*
+                    _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+                }
+            }
+        }
+        impl Eq for #error_ident where #(for<'__a> #error_types: Eq,)* {}
+    )
+}
+
 pub fn field_ident_to_error_variant_ident(field: &syn::Ident) -> syn::Ident {
     syn::Ident::new(
-        format!("Unset{}", snake_to_pascal(field.to_string().as_str())).as_str(),
+        format!("Unset{}", snake_to_pascal(field.unraw().to_string().as_str())).as_str(),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+pub fn field_ident_to_invalid_variant_ident(field: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        format!("Invalid{}", snake_to_pascal(field.unraw().to_string().as_str())).as_str(),
         proc_macro2::Span::call_site(),
     )
 }
 
-fn snake_to_pascal(s: &str) -> String {
+pub fn field_ident_to_parse_variant_ident(field: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        format!("Parse{}", snake_to_pascal(field.unraw().to_string().as_str())).as_str(),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+pub fn snake_to_pascal(s: &str) -> String {
     let mut pascal_case_string = String::new();
     let mut capitalize_next = true;
 
@@ -78,3 +278,20 @@ fn snake_to_pascal(s: &str) -> String {
 
     pascal_case_string
 }
+
+pub fn pascal_to_snake(s: &str) -> String {
+    let mut snake_case_string = String::new();
+
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                snake_case_string.push('_');
+            }
+            snake_case_string.extend(c.to_lowercase());
+        } else {
+            snake_case_string.push(c);
+        }
+    }
+
+    snake_case_string
+}
@@ -0,0 +1,118 @@
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+
+use crate::{
+    attr::Attr,
+    builder_struct::split_generics,
+    data::DeriveData,
+    derive_builder::derive_builder,
+    syn_attribute_helper::construct_doc_comment,
+};
+
+const DATA_ATTR: &str = "builder";
+
+/// Generates a builder for a function or for every associated function of an impl block that
+/// is marked with `#[builder]`.
+pub fn builder_attribute(attr: TokenStream, item: syn::Item) -> syn::Result<TokenStream> {
+    match item {
+        syn::Item::Fn(mut item_fn) => {
+            if item_fn.sig.receiver().is_some() || contains_self(item_fn.sig.to_token_stream()) {
+                return Err(syn::Error::new(
+                    item_fn.sig.ident.span(),
+                    "Put #[builder] on the impl block and mark the associated function with #[builder]",
+                ));
+            }
+            let attr = if attr.is_empty() {
+                None
+            } else {
+                Some(syn::parse2::<Attr>(attr)?)
+            };
+            let data = DeriveData::from_fn(attr, &item_fn.sig, None, DATA_ATTR)?;
+            remove_param_attrs(&mut item_fn.sig);
+            let mut result = item_fn.into_token_stream();
+            derive_builder(data)?.to_tokens(&mut result);
+            Ok(result)
+        }
+        syn::Item::Impl(mut item_impl) => {
+            if !attr.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Options have to be set on the associated functions",
+                ));
+            }
+            let mut builders = TokenStream::new();
+            let mut builder_fns = Vec::new();
+            for item in item_impl.items.iter_mut() {
+                let syn::ImplItem::Method(method) = item else {
+                    continue;
+                };
+                let Some(index) = method.attrs.iter().position(|a| a.path.is_ident(DATA_ATTR))
+                else {
+                    continue;
+                };
+                let method_attr = method.attrs.remove(index);
+                let attr = if method_attr.tokens.is_empty() {
+                    None
+                } else {
+                    Some(method_attr.parse_args::<Attr>()?)
+                };
+                let data = DeriveData::from_fn(
+                    attr,
+                    &method.sig,
+                    Some((&item_impl.self_ty, &item_impl.generics)),
+                    DATA_ATTR,
+                )?;
+                remove_param_attrs(&mut method.sig);
+                if let Some(builder_fn) = build_method_builder_fn(&data, &method.sig) {
+                    builder_fns.push(syn::parse2(builder_fn)?);
+                }
+                derive_builder(data)?.to_tokens(&mut builders);
+            }
+            item_impl.items.extend(builder_fns);
+            let mut result = item_impl.into_token_stream();
+            builders.to_tokens(&mut result);
+            Ok(result)
+        }
+        item => Err(syn::Error::new_spanned(
+            item,
+            "The builder attribute can only be used on functions and impl blocks",
+        )),
+    }
+}
+
+/// Builds the associated function that returns the builder of an associated function.
+fn build_method_builder_fn(data: &DeriveData, sig: &syn::Signature) -> Option<TokenStream> {
+    let builder_fn = data.builder_fn.as_ref()?;
+    let builder_ident = &data.builder_ident;
+    let (_, generics_without_bounds, _) = split_generics(&data.generics);
+    let params = &sig.generics.params;
+    let where_clause = &sig.generics.where_clause;
+    let comment = construct_doc_comment(
+        format!("Create a new {builder_ident} without any values set.").as_str(),
+    );
+    Some(quote::quote!(
+        #comment
+        pub fn #builder_fn<#params>() -> #builder_ident #generics_without_bounds #where_clause {
+            Default::default()
+        }
+    ))
+}
+
+/// Removes the builder and serde options from the parameters, the compiler does not know them.
+fn remove_param_attrs(sig: &mut syn::Signature) {
+    sig.inputs.iter_mut().for_each(|input| {
+        if let syn::FnArg::Typed(input) = input {
+            input
+                .attrs
+                .retain(|attr| !attr.path.is_ident(DATA_ATTR) && !attr.path.is_ident("serde"));
+        }
+    });
+}
+
+fn contains_self(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => ident == "Self",
+        proc_macro2::TokenTree::Group(group) => contains_self(group.stream()),
+        _ => false,
+    })
+}
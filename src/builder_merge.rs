@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    builder_struct::{clone_bounds, split_generics},
+    data::DeriveData,
+    syn_attribute_helper::construct_doc_comment,
+};
+
+/// Builds `merge`, which overlays another builder, and `fill_missing_from`,
+/// which takes unset values from an existing struct.
+pub fn build_merge_functions(data: &DeriveData) -> TokenStream {
+    let fields = data.fields.as_ref().iter().filter(|f| !f.skip).collect::<Vec<_>>();
+    let merged = fields.iter().map(|f| {
+        let ident = &f.ident;
+        if f.collection && data.extend_collections {
+            quote::quote!(
+                #ident: match (self.#ident, other.#ident) {
+                    (Some(mut value), Some(other)) => {
+                        value.extend(other);
+                        Some(value)
+                    }
+                    (value, other) => other.or(value),
+                },
+            )
+        } else {
+            quote::quote!(#ident: other.#ident.or(self.#ident),)
+        }
+    });
+    let merge_comments = [
+        construct_doc_comment("Overlay the values that are set in `other` over the values of this builder."),
+        construct_doc_comment(if data.extend_collections {
+            "Collections that are set in both builders are extended with the values of `other`."
+        } else {
+            "Collections that are set in `other` replace the collections of this builder."
+        }),
+    ];
+    let merge = quote::quote!(
+        #(#merge_comments)*
+        #[must_use]
+        pub fn merge(self, other: Self) -> Self {
+            Self {
+                #(#merged)*
+                __marker: std::marker::PhantomData,
+            }
+        }
+    );
+    // Enum variants and functions have no struct to take the values from.
+    if data.variant.is_some() || data.function.is_some() {
+        return merge;
+    }
+    let struct_ident = &data.struct_ident;
+    let (_, generics_without_bounds, _) = split_generics(&data.generics);
+    let fill = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let member = &f.member;
+        let ty = &f.ty;
+        let value = quote::quote!(<#ty as Clone>::clone(&base.#member));
+        let value = if f.is_optional.is_some() {
+            value
+        } else {
+            quote::quote!(Some(#value))
+        };
+        quote::quote!(
+            if self.#ident.is_none() {
+                self.#ident = #value;
+            }
+        )
+    });
+    let clone_bounds = clone_bounds(&fields);
+    let fill_comment = construct_doc_comment(
+        format!("Set all unset values to copies of the values of the given {struct_ident}.").as_str(),
+    );
+    quote::quote!(
+        #merge
+        #fill_comment
+        #[must_use]
+        pub fn fill_missing_from(mut self, base: &#struct_ident #generics_without_bounds) -> Self
+        where
+            #clone_bounds
+        {
+            #(#fill)*
+            self
+        }
+    )
+}
@@ -0,0 +1,98 @@
+use proc_macro2::TokenStream;
+
+use crate::data::DeriveData;
+
+/// Builds `FIELDS`, `is_set`, `fields_set` and `missing_fields`.
+/// `FIELDS` only uses std types, so tooling can handle the fields of every builder the same way.
+pub fn build_reflection_functions(data: &DeriveData) -> TokenStream {
+    let fields = data
+        .fields
+        .as_ref()
+        .iter()
+        .filter(|f| !f.skip)
+        .collect::<Vec<_>>();
+    let infos = fields.iter().map(|f| {
+        let name = f.name();
+        let ty = type_name(&f.ty);
+        let status = if f.is_required() {
+            "required"
+        } else if f.default_value().is_some() {
+            "default"
+        } else {
+            "optional"
+        };
+        let docs = &f.docs;
+        quote::quote!((#name, #ty, #status, #docs),)
+    });
+    let is_set_arms = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let name = f.name();
+        quote::quote!(#name => self.#ident.is_some(),)
+    });
+    let fields_set = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let name = f.name();
+        quote::quote!(
+            if self.#ident.is_some() {
+                fields.push(#name);
+            }
+        )
+    });
+    let missing_fields = fields.iter().filter(|f| f.is_required()).map(|f| {
+        let ident = &f.ident;
+        let name = f.name();
+        quote::quote!(
+            if self.#ident.is_none() {
+                fields.push(#name);
+            }
+        )
+    });
+    quote::quote!(
+        /// The fields that can be set on the builder as `(name, type, status, docs)`.
+        /// The type is written as in the struct, the status is `"required"` if the value has to
+        /// be set, `"optional"` if it is `None` if not set and `"default"` if the default value
+        /// is used if not set.
+        pub const FIELDS: &'static [(&'static str, &'static str, &'static str, &'static str)] = &[
+            #(#infos)*
+        ];
+        /// Returns if the field named `name` is set. Returns `false` if there is no such field.
+        pub fn is_set(&self, name: &str) -> bool {
+            match name {
+                #(#is_set_arms)*
+                _ => false,
+            }
+        }
+        /// Returns the names of all fields that are set.
+        pub fn fields_set(&self) -> Vec<&'static str> {
+            #[allow(unused_mut)]
+            let mut fields = Vec::new();
+            #(#fields_set)*
+            fields
+        }
+        /// Returns the names of all required fields that are not set.
+        pub fn missing_fields(&self) -> Vec<&'static str> {
+            #[allow(unused_mut)]
+            let mut fields = Vec::new();
+            #(#missing_fields)*
+            fields
+        }
+    )
+}
+
+/// Writes a type without the spaces `to_string` puts between all tokens.
+fn type_name(ty: &syn::Type) -> String {
+    quote::quote!(#ty)
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+        .replace(" ;", ";")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace("( ", "(")
+        .replace(" )", ")")
+}
@@ -0,0 +1,86 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    builder_struct::split_generics,
+    data::{DeriveData, Field},
+};
+
+/// The container options of the struct that also apply to the builder.
+const CONTAINER_OPTIONS: [&str; 2] = ["rename_all", "deny_unknown_fields"];
+
+/// The field options that also apply to the builder. Other options like `default` or `with`
+/// expect the type of the struct field instead of the `Option` of the builder field.
+const FIELD_OPTIONS: [&str; 2] = ["rename", "alias"];
+
+/// The attributes that derive `Serialize` and `Deserialize` for the builder.
+pub fn serde_struct_attrs(data: &DeriveData) -> Vec<syn::Attribute> {
+    let options = serde_options(&data.serde_attrs, &CONTAINER_OPTIONS);
+    let mut attrs = vec![syn::parse_quote!(#[derive(::serde::Serialize, ::serde::Deserialize)])];
+    if !options.is_empty() {
+        attrs.push(syn::parse_quote!(#[serde(#(#options),*)]));
+    }
+    attrs
+}
+
+/// The attributes of a builder field. Unset fields are neither serialized nor required when
+/// deserializing.
+pub fn serde_field_attrs(field: &Field) -> Vec<syn::Attribute> {
+    let options = serde_options(&field.serde_attrs, &FIELD_OPTIONS);
+    let mut attrs = Vec::new();
+    if !options.is_empty() {
+        attrs.push(syn::parse_quote!(#[serde(#(#options),*)]));
+    }
+    attrs.push(syn::parse_quote!(#[serde(default, skip_serializing_if = "Option::is_none")]));
+    attrs
+}
+
+/// The options of `attrs` with one of the given names.
+fn serde_options(attrs: &[syn::Attribute], names: &[&str]) -> Vec<syn::NestedMeta> {
+    attrs
+        .iter()
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .filter(|option| match option {
+            syn::NestedMeta::Meta(meta) => names.iter().any(|name| meta.path().is_ident(name)),
+            syn::NestedMeta::Lit(_) => false,
+        })
+        .collect()
+}
+
+pub fn serde_marker_attrs() -> Vec<syn::Attribute> {
+    vec![syn::parse_quote!(#[serde(skip)])]
+}
+
+/// Implements `Deserialize` for the struct by deserializing the builder and building the struct.
+/// Errors of `try_build` are returned as custom errors of the deserializer.
+pub fn build_deserialize_impl(data: &DeriveData) -> TokenStream {
+    let DeriveData {
+        struct_ident,
+        builder_ident,
+        ..
+    } = data;
+    let (mut generics, generics_without_bounds, where_clause) = split_generics(&data.generics);
+    generics.params.insert(0, syn::parse_quote!('de));
+    let builder_bound = quote::quote!(
+        #builder_ident #generics_without_bounds: ::serde::Deserialize<'de>,
+    );
+    let where_clause = match where_clause {
+        Some(where_clause) => quote::quote!(#where_clause #builder_bound),
+        None => quote::quote!(where #builder_bound),
+    };
+    quote::quote!(
+        impl #generics ::serde::Deserialize<'de> for #struct_ident #generics_without_bounds #where_clause {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let builder =
+                    <#builder_ident #generics_without_bounds>::deserialize(deserializer)?;
+                builder.try_build().map_err(::serde::de::Error::custom)
+            }
+        }
+    )
+}
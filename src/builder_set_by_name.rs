@@ -0,0 +1,127 @@
+use proc_macro2::TokenStream;
+
+use crate::{
+    builder_struct::split_generics,
+    data::DeriveData,
+    syn_attribute_helper::construct_doc_comment,
+};
+
+pub fn set_error_ident(data: &DeriveData) -> syn::Ident {
+    syn::Ident::new(
+        format!("{}SetError", data.builder_ident).as_str(),
+        proc_macro2::Span::call_site(),
+    )
+}
+
+/// Builds the error returned by `set_by_name` and `from_pairs`.
+pub fn build_set_error(data: &DeriveData) -> TokenStream {
+    let set_error_ident = set_error_ident(data);
+    let comment = construct_doc_comment(
+        format!("The error returned if a value of a {} could not be set by name.", data.builder_ident)
+            .as_str(),
+    );
+    quote::quote!(
+        #comment
+        #[derive(Debug, PartialEq, Eq)]
+        pub enum #set_error_ident {
+            /// The builder has no field with the given name.
+            UnknownField(String),
+            /// The value could not be parsed.
+            Parse {
+                field: &'static str,
+                message: String,
+            },
+        }
+        impl std::fmt::Display for #set_error_ident {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    Self::UnknownField(field) => write!(f, "Error unknown field {}", field),
+                    Self::Parse { field, message } => {
+                        write!(f, "Error {} could not be parsed: {}", field, message)
+                    }
+                }
+            }
+        }
+        impl std::error::Error for #set_error_ident {}
+    )
+}
+
+/// Builds `set_by_name` and `from_pairs`, which set values by the name of their field.
+pub fn build_set_by_name_functions(data: &DeriveData) -> TokenStream {
+    let set_error_ident = set_error_ident(data);
+    let (_, _, where_clause) = split_generics(&data.generics);
+    let fields = data
+        .fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.set_by_name && !f.skip)
+        .collect::<Vec<_>>();
+    let bounds = fields.iter().map(|f| {
+        let ty = f.builder_ty();
+        quote::quote!(
+            #ty: std::str::FromStr,
+            <#ty as std::str::FromStr>::Err: std::fmt::Display,
+        )
+    });
+    let where_clause = match where_clause {
+        Some(where_clause) => quote::quote!(#where_clause #(#bounds)*),
+        None => quote::quote!(where #(#bounds)*),
+    };
+    let arms = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let name = f.name();
+        let ty = f.builder_ty();
+        quote::quote!(
+            #name => match <#ty as std::str::FromStr>::from_str(value) {
+                Ok(value) => {
+                    self.#ident = Some(value);
+                    Ok(())
+                }
+                Err(e) => Err(#set_error_ident::Parse {
+                    field: #name,
+                    message: e.to_string(),
+                }),
+            },
+        )
+    });
+    let names = fields
+        .iter()
+        .map(|f| format!("* {}\n", f.name()))
+        .collect::<String>();
+    let set_by_name_comments = [
+        construct_doc_comment("Parse `value` with `FromStr` and set it to the field named `field`."),
+        construct_doc_comment("# Fields"),
+        construct_doc_comment(names.as_str()),
+    ];
+    let from_pairs_comments = [
+        construct_doc_comment(
+            format!("Create a new {} with the values of `pairs` set by name.", data.builder_ident)
+                .as_str(),
+        ),
+        construct_doc_comment("Every pair is a field name and the value of the field, see `set_by_name`."),
+    ];
+    quote::quote!(
+        #(#set_by_name_comments)*
+        pub fn set_by_name(&mut self, field: &str, value: &str) -> Result<(), #set_error_ident>
+        #where_clause
+        {
+            match field {
+                #(#arms)*
+                _ => Err(#set_error_ident::UnknownField(field.to_string())),
+            }
+        }
+        #(#from_pairs_comments)*
+        pub fn from_pairs<I, K, V>(pairs: I) -> Result<Self, #set_error_ident>
+        #where_clause
+            I: IntoIterator<Item = (K, V)>,
+            K: AsRef<str>,
+            V: AsRef<str>,
+        {
+            let mut builder = Self::default();
+            for (field, value) in pairs {
+                builder.set_by_name(field.as_ref(), value.as_ref())?;
+            }
+            Ok(builder)
+        }
+    )
+}
@@ -1,39 +1,58 @@
 use proc_macro2::TokenStream;
 
 use crate::{
-    builder_error_enum::field_ident_to_error_variant_ident,
-    data::Field,
-    syn_attribute_helper::{construct_attribute, construct_doc_comment},
+    builder_accessors::build_accessor_functions,
+    builder_env::build_env_functions,
+    builder_error_enum::{field_ident_to_error_variant_ident, field_ident_to_invalid_variant_ident},
+    builder_merge::build_merge_functions,
+    data::{CollectionItem, DeriveData, Each, Field, Function, Setters, Validator},
+    builder_reflection::build_reflection_functions,
+    builder_serde::{serde_field_attrs, serde_marker_attrs, serde_struct_attrs},
+    builder_set_by_name::build_set_by_name_functions,
+    syn_attribute_helper::construct_doc_comment,
 };
 
-pub fn build_struct(
-    builder_ident: &syn::Ident,
-    fields: &[Field],
-    generics: &syn::Generics,
-) -> syn::ItemStruct {
+pub fn build_struct(data: &DeriveData) -> syn::ItemStruct {
+    let DeriveData {
+        builder_ident,
+        fields,
+        generics,
+        ..
+    } = data;
+    let (_, generics_without_bounds, _) = split_generics(generics);
+    let target_ty = data.target_ty(&generics_without_bounds);
+    let serde = data.serde;
+    let marker = syn::Field {
+        ident: Some(syn::Ident::new("__marker", proc_macro2::Span::call_site())),
+        vis: syn::Visibility::Inherited,
+        attrs: if serde {
+            serde_marker_attrs()
+        } else {
+            Vec::new()
+        },
+        ty: syn::parse_quote!(std::marker::PhantomData<fn() -> #target_ty>),
+        colon_token: Default::default(),
+    };
     let fields = fields
-        .into_iter()
-        .map(
-            |Field {
-                 ident,
-                 default: _,
-                 ty,
-                 is_optional,
-             }| {
-                let ty = if let Some(ty) = is_optional {
-                    ty.clone()
-                } else {
-                    ty.clone()
-                };
-                generate_new_builder_field(ident.clone(), ty)
-            },
-        )
+        .as_ref()
+        .iter()
+        .filter(|f| !f.skip)
+        .map(|field| {
+            let mut builder_field =
+                generate_new_builder_field(field.ident.clone(), field.builder_ty().clone());
+            if serde {
+                builder_field.attrs = serde_field_attrs(field);
+            }
+            builder_field
+        })
+        .chain([marker])
         .collect();
+    let mut attrs = vec![construct_doc_comment("Test comment")];
+    if serde {
+        attrs.extend(serde_struct_attrs(data));
+    }
     syn::ItemStruct {
-        attrs: vec![
-            construct_attribute("derive", &["Default"]),
-            construct_doc_comment("Test comment"),
-        ],
+        attrs,
         fields: syn::Fields::Named(syn::FieldsNamed {
             named: fields,
             brace_token: Default::default(),
@@ -76,35 +95,135 @@ fn generate_new_builder_field(ident: syn::Ident, ty: syn::Type) -> syn::Field {
     }
 }
 
-pub fn build_impl(
-    struct_ident: &syn::Ident,
-    builder_ident: &syn::Ident,
-    setter_attributes: &[Field],
-    required_build_fields: &[Field],
-    error_ident: &syn::Ident,
-    copy_on_build: bool,
-    mut generics: syn::Generics
-) -> TokenStream {
-    generics.params.iter_mut().for_each(|param| {
-        if let syn::GenericParam::Type(ty) = param {
+pub fn build_impl(data: &DeriveData, required_build_fields: &[Field]) -> TokenStream {
+    let DeriveData {
+        builder_ident,
+        fields,
+        ..
+    } = data;
+    let (generics, generics_without_bounds, where_clause) = split_generics(&data.generics);
+    let empty_fields = fields.as_ref().iter().filter(|f| !f.skip).map(|f| {
+        let ident = &f.ident;
+        quote::quote!(#ident: None,)
+    });
+    let empty_builder = quote::quote!(
+        #builder_ident {
+            #(#empty_fields)*
+            __marker: std::marker::PhantomData,
+        }
+    );
+    let constructor = build_constructor(data, &empty_builder);
+    let setter = build_setter_functions(fields.as_ref());
+    let build = build_builder_functions(data, required_build_fields, &generics_without_bounds);
+    let env = build_env_functions(data);
+    let set_by_name = data.set_by_name.then(|| build_set_by_name_functions(data));
+    let reflection = build_reflection_functions(data);
+    let accessors = build_accessor_functions(data);
+    let merge = build_merge_functions(data);
+    let builder_fn = build_builder_fn(data, &quote::quote!(#builder_ident #generics_without_bounds), &empty_builder);
+    quote::quote!(
+        impl #generics Default for #builder_ident #generics_without_bounds #where_clause {
+            fn default() -> Self {
+                #empty_builder
+            }
+        }
+        impl #generics #builder_ident #generics_without_bounds #where_clause {
+            #constructor
+            #setter
+            #accessors
+            #merge
+            #env
+            #set_by_name
+            #reflection
+            #build
+        }
+        #builder_fn
+    )
+}
+
+/// Builds the constructor of a builder without any values set.
+pub fn build_constructor(data: &DeriveData, empty_builder: &TokenStream) -> Option<TokenStream> {
+    let constructor = data.constructor.as_ref()?;
+    let comment = construct_doc_comment(
+        format!("Create a new {} without any values set.", data.builder_ident).as_str(),
+    );
+    Some(quote::quote!(
+        #comment
+        pub fn #constructor() -> Self {
+            #empty_builder
+        }
+    ))
+}
+
+/// Builds the associated function of the struct that returns a builder without any values set.
+pub fn build_builder_fn(
+    data: &DeriveData,
+    builder_type: &TokenStream,
+    empty_builder: &TokenStream,
+) -> Option<TokenStream> {
+    if data.function.is_some() {
+        return None;
+    }
+    let builder_fn = data.builder_fn.as_ref()?;
+    let DeriveData {
+        struct_ident,
+        builder_ident,
+        ..
+    } = data;
+    let (generics, generics_without_bounds, where_clause) = split_generics(&data.generics);
+    let comment = construct_doc_comment(
+        format!("Create a new {builder_ident} without any values set.").as_str(),
+    );
+    Some(quote::quote!(
+        impl #generics #struct_ident #generics_without_bounds #where_clause {
+            #comment
+            pub fn #builder_fn() -> #builder_type {
+                #empty_builder
+            }
+        }
+    ))
+}
+
+/// Splits the generics of the target struct into the generics of an impl block,
+/// the generic arguments of the builder type and the where clause.
+pub fn split_generics(generics: &syn::Generics) -> (syn::Generics, syn::Generics, Option<TokenStream>) {
+    let mut generics = generics.clone();
+    generics.params.iter_mut().for_each(|param| match param {
+        syn::GenericParam::Type(ty) => {
             ty.eq_token = None;
             ty.default = None;
         }
+        syn::GenericParam::Const(c) => {
+            c.eq_token = None;
+            c.default = None;
+        }
+        syn::GenericParam::Lifetime(_) => {}
     });
+    // Const parameters are turned into type parameters, because as a generic argument
+    // only the identifier of the parameter is written.
     let generics_without_bounds = generics.params.iter().map(|p| {
-        if let syn::GenericParam::Type(ty) = p {
-            syn::GenericParam::Type(
-                syn::TypeParam {
-                    attrs: ty.attrs.clone(),
-                    ident: ty.ident.clone(),
+        match p {
+            syn::GenericParam::Type(syn::TypeParam { attrs, ident, .. })
+            | syn::GenericParam::Const(syn::ConstParam { attrs, ident, .. }) => {
+                syn::GenericParam::Type(
+                    syn::TypeParam {
+                        attrs: attrs.clone(),
+                        ident: ident.clone(),
+                        colon_token: None,
+                        bounds: Default::default(),
+                        eq_token: Default::default(),
+                        default: Default::default(),
+                    }
+                )
+            }
+            syn::GenericParam::Lifetime(lifetime) => {
+                syn::GenericParam::Lifetime(syn::LifetimeDef {
+                    attrs: lifetime.attrs.clone(),
+                    lifetime: lifetime.lifetime.clone(),
                     colon_token: None,
                     bounds: Default::default(),
-                    eq_token: Default::default(),
-                    default: Default::default(),
-                }
-            )
-        } else {
-            p.clone()
+                })
+            }
         }
     }).collect::<syn::punctuated::Punctuated<_,_>>();
     let generics_without_bounds = syn::Generics {
@@ -113,16 +232,6 @@ pub fn build_impl(
         gt_token: Default::default(),
         where_clause: Default::default(),
     };
-
-    let setter = build_setter_functions(setter_attributes);
-    let build = build_builder_functions(
-        struct_ident,
-        setter_attributes,
-        required_build_fields,
-        error_ident,
-        copy_on_build,
-        &generics_without_bounds,
-    );
     let where_clause = if let Some(where_clause) = &generics.where_clause {
         let mut ts = quote::quote!(where);
         where_clause.predicates.iter().for_each(|p| {
@@ -132,156 +241,437 @@ pub fn build_impl(
     } else {
         None
     };
-    quote::quote!(
-        impl #generics #builder_ident #generics_without_bounds #where_clause {
-            #setter
-            #build
-        }
-    )
+    (generics, generics_without_bounds, where_clause)
+}
+
+/// Builds `Clone` bounds for the types of `fields`, followed by a comma.
+/// The bounds are higher ranked, so they are not checked for types that are not generic.
+pub fn clone_bounds(fields: &[&Field]) -> TokenStream {
+    let bounds = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote::quote!(for<'__a> #ty: Clone,)
+    });
+    quote::quote!(#(#bounds)*)
 }
 
 fn build_setter_functions(fields: &[Field]) -> proc_macro2::TokenStream {
-    fields.into_iter().fold(
-        proc_macro2::TokenStream::new(),
-        |prev,
-         Field {
-             ident,
-             default,
-             ty,
-             is_optional,
-         }| {
-            let ty = if let Some(ty) = is_optional { ty } else { ty };
+    fields
+        .iter()
+        .filter(|f| !f.skip)
+        .map(build_setter_function)
+        .collect()
+}
 
-            let comment_is_optional = if is_optional.is_some() || default.is_some() {
-                "This value is optional"
-            } else {
-                "This value is required"
-            };
-            let comments = [
-                construct_doc_comment(format!("Set the {ident} to the given value.").as_str()),
-                construct_doc_comment(comment_is_optional),
-            ];
+/// Builds the `with_` and `set_` functions of a field whose builder value is an `Option`.
+pub fn build_setter_function(field: &Field) -> proc_macro2::TokenStream {
+    let Field {
+        ident,
+        is_optional,
+        strip_option,
+        setters,
+        ..
+    } = field;
 
-            let fn_ident_with = syn::Ident::new(format!("with_{}", ident.to_string()).as_str(), ident.span());
-            let fn_ident_set = syn::Ident::new(format!("set_{}", ident.to_string()).as_str(), ident.span());
-            quote::quote!(
-                #prev
-                #(#comments)*
-                #[must_use]
-                pub fn #fn_ident_with(mut self, #ident: #ty) -> Self {
-                    self.#ident = Some(#ident);
-                    self
-                }
+    let comment_is_optional = if !field.is_required() {
+        "This value is optional"
+    } else {
+        "This value is required"
+    };
+    let comments = [
+        construct_doc_comment(format!("Set the {ident} to the given value.").as_str()),
+        construct_doc_comment(comment_is_optional),
+    ];
 
-                #(#comments)*
-                pub fn #fn_ident_set(&mut self, #ident: #ty) {
-                    self.#ident = Some(#ident)
-                }
+    let (ty, value) = if is_optional.is_some() && !strip_option {
+        field.option_setter_argument()
+    } else {
+        let (ty, value) = field.setter_argument();
+        (ty, quote::quote!(Some(#value)))
+    };
+    let setter = build_setter_pair(setters, &comments, ident, &ty, &value);
+    let each_functions = field.each.as_ref().map(|each| build_each_functions(field, each));
+    let setter = quote::quote!(
+        #setter
+        #each_functions
+    );
+    if is_optional.is_none() || !strip_option {
+        return setter;
+    }
+
+    let comments = [
+        construct_doc_comment(format!("Set the {ident} to the given value or unset it with `None`.").as_str()),
+        construct_doc_comment(comment_is_optional),
+    ];
+    let option_setters = Setters {
+        owned: setters
+            .owned
+            .as_ref()
+            .map(|owned| syn::Ident::new(format!("maybe_{owned}").as_str(), owned.span())),
+        mutable: setters
+            .mutable
+            .as_ref()
+            .map(|mutable| syn::Ident::new(format!("{mutable}_opt").as_str(), mutable.span())),
+    };
+    let (ty, value) = field.option_setter_argument();
+    let option_setter = build_setter_pair(&option_setters, &comments, ident, &ty, &value);
+    quote::quote!(
+        #setter
+        #option_setter
+    )
+}
+
+/// Builds the owned and the mutable variant of a function that assigns `value` to the field.
+fn build_setter_pair(
+    setters: &Setters,
+    comments: &[syn::Attribute],
+    ident: &syn::Ident,
+    ty: &TokenStream,
+    value: &TokenStream,
+) -> proc_macro2::TokenStream {
+    let owned = setters.owned.as_ref().map(|fn_ident| {
+        quote::quote!(
+            #(#comments)*
+            #[must_use]
+            pub fn #fn_ident(mut self, #ident: #ty) -> Self {
+                self.#ident = #value;
+                self
+            }
+        )
+    });
+    let mutable = setters.mutable.as_ref().map(|fn_ident| {
+        quote::quote!(
+            #(#comments)*
+            pub fn #fn_ident(&mut self, #ident: #ty) {
+                self.#ident = #value
+            }
+        )
+    });
+    quote::quote!(
+        #owned
+        #mutable
+    )
+}
+
+/// Builds the functions that add single items or iterators to a collection field.
+fn build_each_functions(field: &Field, each: &Each) -> proc_macro2::TokenStream {
+    use syn::ext::IdentExt;
+    let ident = &field.ident;
+    let item_ident = &each.ident;
+    let item_type = |ty: &syn::Type| {
+        if field.into {
+            quote::quote!(impl Into<#ty>)
+        } else {
+            quote::quote!(#ty)
+        }
+    };
+    let item_value = |ident: &syn::Ident| {
+        if field.into {
+            quote::quote!(#ident.into())
+        } else {
+            quote::quote!(#ident)
+        }
+    };
+    let (arguments, item, iter_item) = match &each.item {
+        CollectionItem::Value(ty) => {
+            let arg_ty = item_type(ty);
+            let value = item_value(item_ident);
+            (quote::quote!(#item_ident: #arg_ty), value, quote::quote!(#ty))
+        }
+        CollectionItem::KeyValue(key_ty, value_ty) => {
+            let key_ident = syn::Ident::new("key", proc_macro2::Span::call_site());
+            let value_ident = syn::Ident::new("value", proc_macro2::Span::call_site());
+            let key_arg_ty = item_type(key_ty);
+            let value_arg_ty = item_type(value_ty);
+            let key = item_value(&key_ident);
+            let value = item_value(&value_ident);
+            (
+                quote::quote!(#key_ident: #key_arg_ty, #value_ident: #value_arg_ty),
+                quote::quote!((#key, #value)),
+                quote::quote!((#key_ty, #value_ty)),
             )
-        },
+        }
+    };
+    let fn_ident_extend = syn::Ident::new(format!("extend_{}", ident.unraw()).as_str(), ident.span());
+    let item_comment = construct_doc_comment(format!("Add an item to the {ident}.").as_str());
+    let extend_comment = construct_doc_comment(format!("Add all items of the iterator to the {ident}.").as_str());
+    let with_item = each.setters.owned.as_ref().map(|fn_ident| {
+        quote::quote!(
+            #item_comment
+            #[must_use]
+            pub fn #fn_ident(mut self, #arguments) -> Self {
+                self.#ident.get_or_insert_with(Default::default).extend(std::iter::once(#item));
+                self
+            }
+        )
+    });
+    let add_item = each.setters.mutable.as_ref().map(|fn_ident| {
+        quote::quote!(
+            #item_comment
+            pub fn #fn_ident(&mut self, #arguments) {
+                self.#ident.get_or_insert_with(Default::default).extend(std::iter::once(#item))
+            }
+        )
+    });
+    let extend = if each.setters.owned.is_some() {
+        quote::quote!(
+            #extend_comment
+            #[must_use]
+            pub fn #fn_ident_extend(mut self, #ident: impl IntoIterator<Item = #iter_item>) -> Self {
+                self.#ident.get_or_insert_with(Default::default).extend(#ident);
+                self
+            }
+        )
+    } else {
+        quote::quote!(
+            #extend_comment
+            pub fn #fn_ident_extend(&mut self, #ident: impl IntoIterator<Item = #iter_item>) {
+                self.#ident.get_or_insert_with(Default::default).extend(#ident)
+            }
+        )
+    };
+    quote::quote!(
+        #with_item
+        #add_item
+        #extend
     )
 }
 
 fn build_builder_functions(
-    struct_ident: &syn::Ident,
-    setter_attributes: &[Field],
+    data: &DeriveData,
     required_build_fields: &[Field],
-    error_ident: &syn::Ident,
-    copy_on_build: bool,
     generics: &syn::Generics
 ) -> proc_macro2::TokenStream {
+    let DeriveData {
+        struct_ident,
+        error_ident,
+        copy_on_build,
+        collect_missing,
+        fields,
+        ..
+    } = data;
     let clone_fn = if !copy_on_build {
         proc_macro2::TokenStream::new()
     } else {
         quote::quote!(.clone())
     };
-    let build_body = setter_attributes.iter().fold(
+    // The locals have mixed site hygiene, so they can not collide with names in the expressions
+    // of defaults. The prefix of `__missing` keeps it apart from the locals of the fields.
+    let missing = syn::Ident::new("__missing", proc_macro2::Span::mixed_site());
+    let bindings = fields.as_ref().iter().fold(
         proc_macro2::TokenStream::new(),
-        |prev,
-         Field {
-             ident,
-             default,
-             ty: _,
-             is_optional,
-         }| {
-            if let Some(default) = default {
+        |prev, field| {
+            let Field {
+                ident: field_ident,
+                is_optional,
+                ..
+            } = field;
+            let ident = &binding_ident(field_ident);
+            if let (true, Some(default)) = (field.skip, field.default_value()) {
+                quote::quote!(
+                    #prev
+                    let #ident = #default;
+                )
+            } else if let Some(default) = field.default_value() {
+                quote::quote!(
+                    #prev
+                    let #ident = self.#field_ident #clone_fn.unwrap_or_else(|| #default);
+                )
+            } else if is_optional.is_some() {
+                quote::quote!(
+                    #prev
+                    let #ident = self.#field_ident #clone_fn;
+                )
+            } else if *collect_missing {
+                let name = field.name();
                 quote::quote!(
                     #prev
-                    #ident: self.#ident #clone_fn.unwrap_or_else(|| #default),
+                    let #ident = self.#field_ident #clone_fn;
+                    if #ident.is_none() {
+                        #missing.push(#name);
+                    }
                 )
             } else {
-                if is_optional.is_some() && copy_on_build {
-                    quote::quote!(
-                        #prev
-                        #ident: self.#ident.clone(),
-                    )
-                } else if is_optional.is_some() && !copy_on_build {
-                    quote::quote!(
-                        #prev
-                        #ident: self.#ident,
-                    )
-                } else {
-                    let error_variant_error = field_ident_to_error_variant_ident(ident);
-                    quote::quote!(
-                        #prev
-                        #ident: match self.#ident #clone_fn {
-                            Some(#ident) => #ident,
-                            None => return Err(#error_ident::#error_variant_error)
-                        },
-                    )
-                }
+                let error_variant_error = field_ident_to_error_variant_ident(field_ident);
+                quote::quote!(
+                    #prev
+                    let #ident = match self.#field_ident #clone_fn {
+                        Some(#ident) => #ident,
+                        None => return Err(#error_ident::#error_variant_error)
+                    };
+                )
             }
         },
     );
-    let build_body = required_build_fields.iter().fold(build_body, |prev, Field { ident, default: _, ty: _, is_optional: _}| {
+    let arguments = required_build_fields.iter().map(|f| {
+        let ident = &f.ident;
+        let (ty, _) = f.build_argument();
+        quote::quote!(#ident: #ty)
+    }).collect::<Vec<_>>();
+    let argument_idents = required_build_fields.iter().map(|f| &f.ident).collect::<Vec<_>>();
+    let argument_bindings = required_build_fields.iter().map(|f| {
+        let ident = binding_ident(&f.ident);
+        let (_, value) = f.build_argument();
+        quote::quote!(let #ident = #value;)
+    });
+    let bindings = quote::quote!(
+        #(#argument_bindings)*
+        #bindings
+    );
+    let bindings = if *collect_missing {
+        let unwrap_required = fields.as_ref().iter().filter(|f| f.is_required()).map(|f| {
+            let ident = binding_ident(&f.ident);
+            quote::quote!(let #ident = #ident.unwrap();)
+        });
         quote::quote!(
-            #prev
-            #ident: #ident,
+            let mut #missing = Vec::new();
+            #bindings
+            if !#missing.is_empty() {
+                return Err(#error_ident::Missing(#missing));
+            }
+            #(#unwrap_required)*
         )
+    } else {
+        bindings
+    };
+    let validation = fields.as_ref().iter().chain(required_build_fields).filter_map(|f| {
+        let Validator { path, .. } = f.validate.as_ref()?;
+        let ident = binding_ident(&f.ident);
+        let variant = field_ident_to_invalid_variant_ident(&f.ident);
+        let value = if f.is_optional.is_some() {
+            quote::quote!(#ident.as_ref())
+        } else {
+            quote::quote!(Some(&#ident))
+        };
+        Some(quote::quote!(
+            if let Some(value) = #value {
+                if let Err(e) = #path(value) {
+                    return Err(#error_ident::#variant(e));
+                }
+            }
+        ))
     });
+    let bindings = quote::quote!(
+        #bindings
+        #(#validation)*
+    );
+    let build_body = construct_struct(
+        data,
+        fields.as_ref().iter().chain(required_build_fields).map(|f| {
+            let ident = binding_ident(&f.ident);
+            (f, quote::quote!(#ident))
+        }),
+    );
     
     let try_build_comments = [
-        construct_doc_comment(format!("Construct a new {struct_ident} instance. This function returns an error if not all required values are set").as_str()),
+        construct_doc_comment(format!("{} This function returns an error if not all required values are set or a validation fails", data.build_description()).as_str()),
         construct_doc_comment("# Required values"),
         construct_doc_comment(
-            setter_attributes
+            fields
+                .as_ref()
                 .iter()
-                .filter(|f| f.is_optional.is_none() && f.default.is_none())
-                .map(|f| format!("* {}\n", f.ident.to_string()))
+                .filter(|f| f.is_required())
+                .map(|f| format!("* {}\n", f.ident))
                 .collect::<String>()
                 .as_str(),
         ),
     ];
     let build_comments = [
-        construct_doc_comment(format!("Construct a new {struct_ident} instance.").as_str()),
+        construct_doc_comment(data.build_description().as_str()),
         construct_doc_comment("# Required values"),
         construct_doc_comment(
-            setter_attributes
+            fields
+                .as_ref()
                 .iter()
-                .filter(|f| f.is_optional.is_none() && f.default.is_none())
-                .map(|f| format!("* {}\n", f.ident.to_string()))
+                .filter(|f| f.is_required())
+                .map(|f| format!("* {}\n", f.ident))
                 .collect::<String>()
                 .as_str(),
         ),
         construct_doc_comment("# Panics"),
-        construct_doc_comment("This function may panic if not all required values are set."),
+        construct_doc_comment("This function panics if not all required values are set or a validation fails."),
     ];
-    let self_token = if copy_on_build {
+    let self_token = if *copy_on_build {
         quote::quote!(&self)
     } else {
         quote::quote!(self)
     };
+    let target_ty = data.target_ty(generics);
+    let (try_build, build) = data.build_fn_idents();
+    let construct = if let Some(Validator { path, .. }) = &data.validate {
+        let path = replace_self_in_path(path, struct_ident, generics);
+        quote::quote!(
+            let value = #build_body;
+            if let Err(e) = #path(&value) {
+                return Err(#error_ident::Validation(e));
+            }
+            Ok(value)
+        )
+    } else {
+        quote::quote!(
+            Ok(#build_body)
+        )
+    };
     quote::quote!(
             #(#try_build_comments)*
-            pub fn try_build(#self_token) -> Result<#struct_ident #generics, #error_ident> {
-                Ok(#struct_ident {
-                    #build_body
-                })
+            pub fn #try_build(#self_token, #(#arguments),*) -> Result<#target_ty, #error_ident> {
+                #bindings
+                #construct
             }
             #(#build_comments)*
-            pub fn build(#self_token) -> #struct_ident #generics {
-                self.try_build().unwrap()
+            pub fn #build(#self_token, #(#arguments),*) -> #target_ty {
+                match self.#try_build(#(#argument_idents),*) {
+                    Ok(value) => value,
+                    Err(e) => panic!("{}", e),
+                }
             }
     )
     
 }
+
+/// The local a value of a field is bound to in `try_build`.
+fn binding_ident(ident: &syn::Ident) -> syn::Ident {
+    let mut ident = ident.clone();
+    ident.set_span(proc_macro2::Span::mixed_site());
+    ident
+}
+
+/// Constructs the struct or enum variant or calls the function with one value per field.
+/// Tuple structs are constructed with the tuple constructor, all other structs with named fields.
+pub fn construct_struct<'a>(
+    data: &DeriveData,
+    values: impl Iterator<Item = (&'a Field, TokenStream)>,
+) -> TokenStream {
+    let struct_ident = &data.struct_ident;
+    let path = match (&data.function, &data.variant) {
+        (Some(Function { path, .. }), _) => path.clone(),
+        (None, Some(variant)) => quote::quote!(#struct_ident::#variant),
+        (None, None) => quote::quote!(#struct_ident),
+    };
+    if data.tuple {
+        let mut values = values.collect::<Vec<_>>();
+        values.sort_by_key(|(field, _)| match &field.member {
+            syn::Member::Unnamed(index) => index.index,
+            syn::Member::Named(_) => 0,
+        });
+        let values = values.into_iter().map(|(_, value)| value);
+        quote::quote!(#path(#(#values),*))
+    } else {
+        let values = values.map(|(field, value)| {
+            let member = &field.member;
+            quote::quote!(#member: #value)
+        });
+        quote::quote!(#path { #(#values),* })
+    }
+}
+
+/// Replaces a leading `Self` in `path`, because inside the builder `Self` refers to the builder
+/// instead of the struct.
+fn replace_self_in_path(path: &syn::Path, struct_ident: &syn::Ident, generics: &syn::Generics) -> TokenStream {
+    match path.segments.first() {
+        Some(first) if first.ident == "Self" && path.leading_colon.is_none() => {
+            let rest = path.segments.iter().skip(1);
+            quote::quote!(<#struct_ident #generics>#(::#rest)*)
+        }
+        _ => quote::quote!(#path),
+    }
+}
@@ -0,0 +1,239 @@
+use proc_macro2::TokenStream;
+use syn::ext::IdentExt;
+
+use crate::{
+    builder_error_enum::snake_to_pascal,
+    builder_struct::{
+        build_builder_fn, build_constructor, build_setter_function, construct_struct,
+        split_generics,
+    },
+    data::{DeriveData, Field},
+    syn_attribute_helper::construct_doc_comment,
+};
+
+/// Builds a builder that tracks the state of every required field in its own type parameter.
+/// An unset field has the type `()`, a set field the type of the struct field.
+pub fn build_typestate_struct(data: &DeriveData) -> TokenStream {
+    let DeriveData {
+        builder_ident,
+        fields,
+        generics,
+        ..
+    } = data;
+    let mut struct_generics = generics.clone();
+    fields
+        .as_ref()
+        .iter()
+        .filter(|f| f.is_required())
+        .for_each(|f| {
+            let state = field_ident_to_state_ident(&f.ident);
+            struct_generics
+                .params
+                .push(syn::GenericParam::Type(syn::parse_quote!(#state = ())));
+        });
+    let where_clause = &generics.where_clause;
+    let (_, generics_without_bounds, _) = split_generics(generics);
+    let target_ty = data.target_ty(&generics_without_bounds);
+    let fields = fields.as_ref().iter().filter(|f| !f.skip).map(|f| {
+        let ident = &f.ident;
+        if f.is_required() {
+            let state = field_ident_to_state_ident(ident);
+            quote::quote!(#ident: #state,)
+        } else {
+            let ty = f.builder_ty();
+            quote::quote!(#ident: Option<#ty>,)
+        }
+    });
+    let comment = construct_doc_comment(
+        "Required values are tracked in the type of the builder. `build` is only available once all of them are set.",
+    );
+    quote::quote!(
+        #comment
+        pub struct #builder_ident #struct_generics #where_clause {
+            #(#fields)*
+            __marker: std::marker::PhantomData<fn() -> #target_ty>,
+        }
+    )
+}
+
+pub fn build_typestate_impl(data: &DeriveData) -> TokenStream {
+    let DeriveData {
+        builder_ident,
+        copy_on_build,
+        fields,
+        ..
+    } = data;
+    let fields = fields.as_ref();
+    let (generics, generics_without_bounds, where_clause) = split_generics(&data.generics);
+    let states = fields
+        .iter()
+        .filter(|f| f.is_required())
+        .map(|f| field_ident_to_state_ident(&f.ident))
+        .collect::<Vec<_>>();
+    let builder_type = |states: &[TokenStream]| {
+        let args = generics_without_bounds
+            .params
+            .iter()
+            .map(|p| quote::quote!(#p))
+            .chain(states.iter().cloned());
+        quote::quote!(#builder_ident<#(#args),*>)
+    };
+
+    let unset_builder = builder_type(
+        &states.iter().map(|_| quote::quote!(())).collect::<Vec<_>>(),
+    );
+    let unset_fields = fields.iter().filter(|f| !f.skip).map(|f| {
+        let ident = &f.ident;
+        if f.is_required() {
+            quote::quote!(#ident: (),)
+        } else {
+            quote::quote!(#ident: None,)
+        }
+    });
+    let empty_builder = quote::quote!(
+        #builder_ident {
+            #(#unset_fields)*
+            __marker: std::marker::PhantomData,
+        }
+    );
+    let constructor = build_constructor(data, &empty_builder);
+    let builder_fn = build_builder_fn(data, &unset_builder, &empty_builder);
+    let default_impl = quote::quote!(
+        impl #generics Default for #unset_builder #where_clause {
+            fn default() -> Self {
+                #empty_builder
+            }
+        }
+        impl #generics #unset_builder #where_clause {
+            #constructor
+        }
+        #builder_fn
+    );
+
+    let mut setter_generics = generics.clone();
+    states.iter().for_each(|state| {
+        setter_generics
+            .params
+            .push(syn::GenericParam::Type(syn::parse_quote!(#state)));
+    });
+    let state_args = states.iter().map(|s| quote::quote!(#s)).collect::<Vec<_>>();
+    let generic_builder = builder_type(&state_args);
+    let setter = fields.iter().filter(|f| !f.skip).map(|field| {
+        let ident = &field.ident;
+        let comments = setter_comments(field);
+        let (argument_ty, value) = field.setter_argument();
+        if let (true, Some(fn_ident_with)) = (field.is_required(), &field.setters.owned) {
+            let ty = &field.ty;
+            let state = field_ident_to_state_ident(ident);
+            let next_states = states
+                .iter()
+                .map(|s| if *s == state { quote::quote!(#ty) } else { quote::quote!(#s) })
+                .collect::<Vec<_>>();
+            let next_builder = builder_type(&next_states);
+            let other_fields = fields.iter().filter(|f| f.ident != *ident && !f.skip).map(|f| {
+                let ident = &f.ident;
+                quote::quote!(#ident: self.#ident,)
+            });
+            quote::quote!(
+                #(#comments)*
+                #[must_use]
+                pub fn #fn_ident_with(self, #ident: #argument_ty) -> #next_builder {
+                    #builder_ident {
+                        #ident: #value,
+                        #(#other_fields)*
+                        __marker: std::marker::PhantomData,
+                    }
+                }
+            )
+        } else {
+            build_setter_function(field)
+        }
+    });
+    let setter_impl = quote::quote!(
+        impl #setter_generics #generic_builder #where_clause {
+            #(#setter)*
+        }
+    );
+
+    let clone_fn = if *copy_on_build {
+        quote::quote!(.clone())
+    } else {
+        TokenStream::new()
+    };
+    let self_token = if *copy_on_build {
+        quote::quote!(&self)
+    } else {
+        quote::quote!(self)
+    };
+    let set_builder = builder_type(
+        &fields
+            .iter()
+            .filter(|f| f.is_required())
+            .map(|f| {
+                let ty = &f.ty;
+                quote::quote!(#ty)
+            })
+            .collect::<Vec<_>>(),
+    );
+    let build_body = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let value = if let (true, Some(default)) = (field.skip, field.default_value()) {
+            quote::quote!(#default)
+        } else if let Some(default) = field.default_value() {
+            quote::quote!(self.#ident #clone_fn.unwrap_or_else(|| #default))
+        } else {
+            quote::quote!(self.#ident #clone_fn)
+        };
+        (field, value)
+    });
+    let arguments = data.required_args.as_ref().iter().map(|f| {
+        let ident = &f.ident;
+        let (ty, _) = f.build_argument();
+        quote::quote!(#ident: #ty)
+    });
+    let argument_fields = data.required_args.as_ref().iter().map(|f| {
+        let (_, value) = f.build_argument();
+        (f, value)
+    });
+    let construct = construct_struct(data, build_body.chain(argument_fields));
+    let target_ty = data.target_ty(&generics_without_bounds);
+    let (_, build) = data.build_fn_idents();
+    let build_comments = [
+        construct_doc_comment(data.build_description().as_str()),
+        construct_doc_comment("This function is only available once all required values are set."),
+    ];
+    let build_impl = quote::quote!(
+        impl #generics #set_builder #where_clause {
+            #(#build_comments)*
+            pub fn #build(#self_token, #(#arguments),*) -> #target_ty {
+                #construct
+            }
+        }
+    );
+
+    quote::quote!(
+        #default_impl
+        #setter_impl
+        #build_impl
+    )
+}
+
+fn setter_comments(field: &Field) -> [syn::Attribute; 2] {
+    let ident = &field.ident;
+    let comment_is_optional = if field.is_required() {
+        "This value is required"
+    } else {
+        "This value is optional"
+    };
+    [
+        construct_doc_comment(format!("Set the {ident} to the given value.").as_str()),
+        construct_doc_comment(comment_is_optional),
+    ]
+}
+
+fn field_ident_to_state_ident(field: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(
+        format!("__{}", snake_to_pascal(field.unraw().to_string().as_str())).as_str(),
+        proc_macro2::Span::call_site(),
+    )
+}
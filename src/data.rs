@@ -1,12 +1,53 @@
-use syn::DeriveInput;
+use syn::{ext::IdentExt, DeriveInput};
+
+use crate::builder_error_enum::{pascal_to_snake, snake_to_pascal};
 
 pub struct DeriveData {
     pub struct_ident: syn::Ident,
+    /// The enum variant that is constructed if the builder is derived for an enum.
+    pub variant: Option<syn::Ident>,
     pub builder_ident: syn::Ident,
     pub error_ident: syn::Ident,
     pub copy_on_build: bool,
+    pub collect_missing: bool,
+    pub typestate: bool,
+    /// The builder implements `Serialize` and `Deserialize`.
+    pub serde: bool,
+    /// The struct implements `Deserialize` by deserializing the builder.
+    pub deserialize: bool,
+    /// The builder has `set_by_name` and `from_pairs` to set values by the name of the field.
+    pub set_by_name: bool,
+    /// `merge` extends collections that are set in both builders instead of replacing them.
+    pub extend_collections: bool,
+    pub validate: Option<Validator>,
+    /// The name of the constructor of the builder.
+    pub constructor: Option<syn::Ident>,
+    /// The name of the associated function of the struct that returns a builder.
+    pub builder_fn: Option<syn::Ident>,
+    /// The name of the function of the struct that turns it into a builder.
+    pub into_builder_fn: Option<syn::Ident>,
+    /// The name of the function of the struct that creates a builder from a copy of it.
+    pub to_builder_fn: Option<syn::Ident>,
     pub generics: syn::Generics,
+    /// The struct is a tuple struct and is constructed with the tuple constructor.
+    pub tuple: bool,
     pub fields: Fields,
+    /// Fields that are passed to `build` and `try_build` as arguments instead of being set on the builder.
+    pub required_args: Fields,
+    /// The function that is called instead of constructing a struct if the builder is generated for
+    /// a function.
+    pub function: Option<Function>,
+    /// The `serde` attributes of the struct or the enum variant.
+    pub serde_attrs: Vec<syn::Attribute>,
+}
+
+/// A function whose parameters are the fields of a builder.
+pub struct Function {
+    pub ident: syn::Ident,
+    /// The path used to call the function.
+    pub path: proc_macro2::TokenStream,
+    /// The return type of the function.
+    pub output: syn::Type,
 }
 
 pub struct Fields {
@@ -21,93 +62,613 @@ impl AsRef<[Field]> for Fields {
 
 #[derive(Clone)]
 pub struct Field {
+    /// The name of the field in the builder. Tuple struct fields are named `field0`, `field1`, ...
+    /// unless a `name` is given.
     pub ident: syn::Ident,
+    /// The field of the struct.
+    pub member: syn::Member,
     pub default: Option<syn::Expr>,
     pub ty: syn::Type,
     pub is_optional: Option<syn::Type>,
+    pub validate: Option<Validator>,
+    pub into: bool,
+    pub strip_option: bool,
+    pub each: Option<Each>,
+    /// The field has no setters and is always filled with its default value.
+    pub skip: bool,
+    pub setters: Setters,
+    pub required_arg: bool,
+    /// The `serde` attributes of the field.
+    pub serde_attrs: Vec<syn::Attribute>,
+    /// The environment variable the value is read from.
+    pub env: Option<String>,
+    /// The value can be set with `set_by_name`.
+    pub set_by_name: bool,
+    /// The documentation of the field.
+    pub docs: String,
+    /// The field is a `Vec`, `HashSet`, `BTreeSet`, `HashMap` or `BTreeMap`.
+    pub collection: bool,
+}
+
+/// The names of the setters of a field. A setter is not generated if its name is `None`.
+#[derive(Clone)]
+pub struct Setters {
+    /// Takes `self` and returns the builder.
+    pub owned: Option<syn::Ident>,
+    /// Takes `&mut self`.
+    pub mutable: Option<syn::Ident>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SetterStyle {
+    Owned,
+    Mutable,
+    Both,
+}
+
+/// The setter options that can be set for the whole struct and overridden per field.
+#[derive(Default)]
+pub struct SetterOptions {
+    style: Option<SetterStyle>,
+    prefix: Option<syn::LitStr>,
+    mut_prefix: Option<syn::LitStr>,
+}
+
+/// Functions that add single items to a collection field.
+#[derive(Clone)]
+pub struct Each {
+    pub ident: syn::Ident,
+    pub item: CollectionItem,
+    pub setters: Setters,
+}
+
+#[derive(Clone)]
+pub enum CollectionItem {
+    /// The item of a `Vec`, `HashSet` or `BTreeSet`.
+    Value(Box<syn::Type>),
+    /// The key and value of a `HashMap` or `BTreeMap`.
+    KeyValue(Box<syn::Type>, Box<syn::Type>),
+}
+
+/// A user supplied function that checks a value and returns `Result<(), error>`.
+#[derive(Clone)]
+pub struct Validator {
+    pub path: syn::Path,
+    pub error: syn::Type,
+}
+
+impl Field {
+    /// The name of the field as it is reported to users, without the `r#` of raw identifiers.
+    pub fn name(&self) -> String {
+        self.ident.unraw().to_string()
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.default_value().is_none() && self.is_optional.is_none()
+    }
+
+    /// The expression used if the value is not set. Unset collections with item setters are empty.
+    pub fn default_value(&self) -> Option<proc_macro2::TokenStream> {
+        match (&self.default, &self.each, &self.is_optional) {
+            (Some(default), _, _) => Some(quote::quote!(#default)),
+            (None, _, _) if self.skip => Some(quote::quote!(Default::default())),
+            (None, Some(_), None) => Some(quote::quote!(Default::default())),
+            _ => None,
+        }
+    }
+
+    /// The type of the value stored in the builder.
+    pub fn builder_ty(&self) -> &syn::Type {
+        self.is_optional.as_ref().unwrap_or(&self.ty)
+    }
+
+    /// The argument type of the setters and the expression converting the argument into
+    /// the stored value.
+    pub fn setter_argument(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let ident = &self.ident;
+        let ty = self.builder_ty();
+        if self.into {
+            (quote::quote!(impl Into<#ty>), quote::quote!(#ident.into()))
+        } else {
+            (quote::quote!(#ty), quote::quote!(#ident))
+        }
+    }
+
+    /// The argument type of `build` if the field is a required argument and the expression
+    /// converting the argument into the value of the field.
+    pub fn build_argument(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let ident = &self.ident;
+        let ty = &self.ty;
+        if self.into {
+            (quote::quote!(impl Into<#ty>), quote::quote!(#ident.into()))
+        } else {
+            (quote::quote!(#ty), quote::quote!(#ident))
+        }
+    }
+
+    /// Like [Field::setter_argument], but the argument and the converted value are wrapped in
+    /// an `Option`.
+    pub fn option_setter_argument(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let ident = &self.ident;
+        let ty = self.builder_ty();
+        if self.into {
+            (
+                quote::quote!(Option<impl Into<#ty>>),
+                quote::quote!(#ident.map(Into::into)),
+            )
+        } else {
+            (quote::quote!(Option<#ty>), quote::quote!(#ident))
+        }
+    }
 }
 
+#[derive(Default)]
 pub struct StructAttrs {
     builder_ident: Option<syn::Ident>,
     error_ident: Option<proc_macro2::Ident>,
-    copy: bool
+    copy: bool,
+    collect_missing: bool,
+    typestate: bool,
+    serde: Option<syn::LitBool>,
+    deserialize: Option<syn::LitBool>,
+    validate: Option<Validator>,
+    into: bool,
+    setter: SetterOptions,
+    constructor: Option<FunctionName>,
+    builder_fn: Option<FunctionName>,
+    into_builder: Option<FunctionName>,
+    to_builder: Option<FunctionName>,
+    env_prefix: Option<syn::LitStr>,
+    set_by_name: bool,
+    extend_collections: bool,
+}
+
+/// The name of a generated function, `true` for the default name or `false` if the function
+/// should not be generated.
+pub enum FunctionName {
+    Ident(syn::Ident),
+    Enabled(bool),
+}
+
+/// The environment variable of a field, `true` for the default name or `false` if the field
+/// should not be read from the environment.
+pub enum EnvVar {
+    Name(syn::LitStr),
+    Enabled(syn::LitBool),
 }
 
+#[derive(Default)]
 pub struct FieldAttrs {
     default: Option<syn::Expr>,
+    validate: Option<Validator>,
+    into: Option<bool>,
+    strip_option: Option<syn::LitBool>,
+    each: Option<syn::Ident>,
+    skip: bool,
+    setter: SetterOptions,
+    setter_name: Option<syn::Ident>,
+    required_arg: Option<syn::LitBool>,
+    name: Option<syn::Ident>,
+    env: Option<EnvVar>,
+    set_by_name: Option<syn::LitBool>,
 }
 
 impl DeriveData {
-    pub fn new(di: DeriveInput, data_attr: &str) -> Result<Self, syn::Error> {
-        let attrs = StructAttrs::new(di.attrs.as_slice(), data_attr)?;
-        let struct_ident = di.ident.clone();
-        let builder_ident = if let Some(bi) = attrs.builder_ident {
+    /// Collects the data of every builder to generate. A struct has one builder, an enum has a
+    /// builder for every variant with fields.
+    pub fn from_input(di: DeriveInput, data_attr: &str) -> Result<Vec<Self>, syn::Error> {
+        let attr = find_attr::<crate::attr::Attr>(di.attrs.as_slice(), data_attr).transpose()?;
+        match &di.data {
+            syn::Data::Struct(data) => {
+                let attrs = StructAttrs::new(attr.as_ref())?;
+                let mut data = Self::new(
+                    &di.ident,
+                    None,
+                    &di.generics,
+                    &data.fields,
+                    attrs,
+                    "builder",
+                    data_attr,
+                )?;
+                data.serde_attrs = serde_attrs(di.attrs.as_slice());
+                Ok(vec![data])
+            }
+            syn::Data::Enum(data) => {
+                if let Some(key) = attr
+                    .as_ref()
+                    .and_then(|attr| attr.find_key(&["builder_ident", "error_ident", "builder_fn"]))
+                {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        format!("{key} has to be set on a variant"),
+                    ));
+                }
+                data.variants
+                    .iter()
+                    .filter(|variant| !matches!(variant.fields, syn::Fields::Unit))
+                    .map(|variant| {
+                        let variant_attr =
+                            find_attr::<crate::attr::Attr>(variant.attrs.as_slice(), data_attr)
+                                .transpose()?;
+                        let attr = match (variant_attr, attr.clone()) {
+                            (Some(variant_attr), Some(attr)) => Some(variant_attr.merge(attr)),
+                            (variant_attr, attr) => variant_attr.or(attr),
+                        };
+                        let attrs = StructAttrs::new(attr.as_ref())?;
+                        let default_builder_fn = format!(
+                            "{}_builder",
+                            pascal_to_snake(variant.ident.to_string().as_str())
+                        );
+                        let mut data = Self::new(
+                            &di.ident,
+                            Some(&variant.ident),
+                            &di.generics,
+                            &variant.fields,
+                            attrs,
+                            default_builder_fn.as_str(),
+                            data_attr,
+                        )?;
+                        data.serde_attrs = serde_attrs(variant.attrs.as_slice());
+                        Ok(data)
+                    })
+                    .collect()
+            }
+            syn::Data::Union(_) => Err(syn::Error::new(
+                di.ident.span(),
+                "Can not derive Builder for union",
+            )),
+        }
+    }
+
+    /// Collects the data of the builder of a function. `impl_block` is the self type and the
+    /// generics of the impl block if the function is an associated function.
+    pub fn from_fn(
+        attr: Option<crate::attr::Attr>,
+        sig: &syn::Signature,
+        impl_block: Option<(&syn::Type, &syn::Generics)>,
+        data_attr: &str,
+    ) -> Result<Self, syn::Error> {
+        if let Some(asyncness) = sig.asyncness {
+            return Err(syn::Error::new(
+                asyncness.span,
+                "Can not generate a builder for an async function",
+            ));
+        }
+        if let Some(key) = attr.as_ref().and_then(|attr| attr.find_key(&["validate"])) {
+            return Err(syn::Error::new(
+                key.span(),
+                "A function builder can only validate parameters",
+            ));
+        }
+        if let Some(key) = attr.as_ref().and_then(|attr| attr.find_key(&["deserialize"])) {
+            return Err(syn::Error::new(
+                key.span(),
+                "deserialize can only be used on structs",
+            ));
+        }
+        let replace_self = |ty: &syn::Type| match impl_block {
+            Some((self_ty, _)) => syn::parse2(replace_self_in_tokens(quote::quote!(#ty), self_ty)),
+            None => Ok(ty.clone()),
+        };
+        let mut generics = sig.generics.clone();
+        if let Some((_, impl_generics)) = impl_block {
+            generics.params = impl_generics
+                .params
+                .iter()
+                .chain(&sig.generics.params)
+                .cloned()
+                .collect();
+            let predicates = impl_generics
+                .where_clause
+                .iter()
+                .chain(&sig.generics.where_clause)
+                .flat_map(|where_clause| where_clause.predicates.iter().cloned())
+                .collect::<syn::punctuated::Punctuated<_, syn::Token![,]>>();
+            generics.where_clause = if predicates.is_empty() {
+                None
+            } else {
+                Some(syn::parse_quote!(where #predicates))
+            };
+        }
+        let unnamed = sig
+            .inputs
+            .iter()
+            .map(|input| {
+                let syn::FnArg::Typed(input) = input else {
+                    return Err(syn::Error::new_spanned(
+                        input,
+                        "Can not generate a builder for a method with a self parameter",
+                    ));
+                };
+                let syn::Pat::Ident(syn::PatIdent { ident, .. }) = input.pat.as_ref() else {
+                    return Err(syn::Error::new_spanned(
+                        &input.pat,
+                        "Parameters of a function with a builder need to be identifiers",
+                    ));
+                };
+                if let syn::Type::ImplTrait(ty) = input.ty.as_ref() {
+                    return Err(syn::Error::new_spanned(
+                        ty,
+                        "impl Trait parameters can not be stored in a builder, use a generic parameter",
+                    ));
+                }
+                Ok(syn::Field {
+                    attrs: input.attrs.clone(),
+                    vis: syn::Visibility::Inherited,
+                    ident: Some(ident.clone()),
+                    colon_token: Some(input.colon_token),
+                    ty: replace_self(&input.ty)?,
+                })
+            })
+            .collect::<Result<_, syn::Error>>()?;
+        let fields = syn::Fields::Unnamed(syn::FieldsUnnamed {
+            paren_token: Default::default(),
+            unnamed,
+        });
+        let fn_ident = &sig.ident;
+        let pascal_fn_ident = snake_to_pascal(fn_ident.unraw().to_string().as_str());
+        let (builder_name, path) = match impl_block {
+            Some((self_ty, _)) => {
+                let self_name = match self_ty {
+                    syn::Type::Path(syn::TypePath { path, .. }) => path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident.to_string())
+                        .unwrap_or_default(),
+                    _ => String::new(),
+                };
+                let builder_name = if fn_ident == "new" {
+                    format!("{self_name}Builder")
+                } else {
+                    format!("{self_name}{pascal_fn_ident}Builder")
+                };
+                (builder_name, quote::quote!(<#self_ty>::#fn_ident))
+            }
+            None => (format!("{pascal_fn_ident}Builder"), quote::quote!(#fn_ident)),
+        };
+        let output = match &sig.output {
+            syn::ReturnType::Default => syn::parse_quote!(()),
+            syn::ReturnType::Type(_, ty) => replace_self(ty)?,
+        };
+        // Elided lifetimes of references are named, because the parameters are stored in the
+        // builder. The output refers to the same lifetime as the parameters.
+        let (fields, output) = {
+            let lifetime: syn::Lifetime = syn::parse_quote!('__builder);
+            let mut fields = fields;
+            let mut output = output;
+            let mut named = name_elided_lifetimes(&mut output, &lifetime);
+            for field in fields.iter_mut() {
+                named |= name_elided_lifetimes(&mut field.ty, &lifetime);
+            }
+            if named {
+                generics.params.insert(0, syn::parse_quote!(#lifetime));
+            }
+            (fields, output)
+        };
+        let mut attrs = StructAttrs::new(attr.as_ref())?;
+        attrs.builder_ident.get_or_insert_with(|| {
+            syn::Ident::new(builder_name.as_str(), proc_macro2::Span::call_site())
+        });
+        if let (None, Some(key)) = (
+            impl_block,
+            attr.as_ref().and_then(|attr| attr.find_key(&["builder_fn"])),
+        ) {
+            return Err(syn::Error::new(
+                key.span(),
+                "builder_fn can only be used on associated functions",
+            ));
+        }
+        let default_builder_fn = if fn_ident == "new" {
+            String::from("builder")
+        } else {
+            format!("{}_builder", fn_ident.unraw())
+        };
+        let mut data = Self::new(
+            fn_ident,
+            None,
+            &generics,
+            &fields,
+            attrs,
+            default_builder_fn.as_str(),
+            data_attr,
+        )?;
+        if impl_block.is_none() {
+            data.builder_fn = None;
+        }
+        data.function = Some(Function {
+            ident: fn_ident.clone(),
+            path,
+            output,
+        });
+        Ok(data)
+    }
+
+    fn new(
+        struct_ident: &syn::Ident,
+        variant: Option<&syn::Ident>,
+        generics: &syn::Generics,
+        fields: &syn::Fields,
+        attrs: StructAttrs,
+        default_builder_fn: &str,
+        data_attr: &str,
+    ) -> Result<Self, syn::Error> {
+        let span = variant.unwrap_or(struct_ident).span();
+        let builder_ident = if let Some(bi) = attrs.builder_ident.clone() {
             bi
         } else {
-            let bi = format!("{}Builder", di.ident.to_string());
+            let variant_name = variant.map(ToString::to_string).unwrap_or_default();
+            let bi = format!("{struct_ident}{variant_name}Builder");
             syn::Ident::new(bi.as_str(), proc_macro2::Span::call_site())
         };
-        let error_ident = if let Some(ei) = attrs.error_ident {
+        let error_ident = if let Some(ei) = attrs.error_ident.clone() {
             ei
         } else {
-            let ei = format!("{}Error", builder_ident.to_string());
+            let ei = format!("{builder_ident}Error");
             syn::Ident::new(ei.as_str(), proc_macro2::Span::call_site())
         };
-        let generics = di.generics;
-        let fields = match &di.data {
-            syn::Data::Struct(syn::DataStruct {
-                fields: syn::Fields::Named(fields),
-                ..
-            }) => Fields::new(fields, data_attr)?,
-            syn::Data::Struct(syn::DataStruct {
-                fields: syn::Fields::Unnamed(fields),
-                ..
-            }) => Fields::new_unnamed(fields, data_attr)?,
-            syn::Data::Struct(syn::DataStruct {
-                fields: syn::Fields::Unit,
-                ..
-            }) => Fields {
-                fields: Vec::default(),
-            },
-            syn::Data::Enum(_) => {
+        if attrs.typestate && attrs.collect_missing {
+            return Err(syn::Error::new(
+                span,
+                "A typestate builder can not be combined with collect_missing",
+            ));
+        }
+        if attrs.typestate && attrs.validate.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "A typestate builder can not validate values",
+            ));
+        }
+        let deserialize = attrs.deserialize.as_ref().filter(|deserialize| deserialize.value);
+        if let (Some(deserialize), Some(_)) = (deserialize, variant) {
+            return Err(syn::Error::new(
+                deserialize.span,
+                "deserialize can not be used on enums",
+            ));
+        }
+        // Deserializing the struct needs a deserializable builder.
+        let serde = match deserialize.or(attrs.serde.as_ref()) {
+            Some(serde) if serde.value && !cfg!(feature = "serde") => {
                 return Err(syn::Error::new(
-                    di.ident.span(),
-                    "Can not derive Builder for enum",
+                    serde.span,
+                    "serde requires the serde feature of jbe",
                 ))
             }
-            syn::Data::Union(_) => {
+            Some(serde) if serde.value && attrs.typestate => {
                 return Err(syn::Error::new(
-                    di.ident.span(),
-                    "Can not derive Builder for union",
+                    serde.span,
+                    "A typestate builder can not be serialized",
                 ))
             }
+            Some(serde) => serde.value,
+            None => false,
         };
+        let deserialize = deserialize.is_some();
+        let fields = match fields {
+            syn::Fields::Named(fields) => Fields::new(fields, &attrs, data_attr)?,
+            syn::Fields::Unnamed(fields) => Fields::new_unnamed(fields, &attrs, data_attr)?,
+            syn::Fields::Unit => Fields {
+                fields: Vec::default(),
+            },
+        };
+        let tuple = fields
+            .fields
+            .iter()
+            .any(|f| matches!(f.member, syn::Member::Unnamed(_)));
+
+        let (required_args, fields): (Vec<_>, _) =
+            fields.fields.into_iter().partition(|f| f.required_arg);
+        if let (true, Some(field)) = (deserialize, required_args.first()) {
+            return Err(syn::Error::new(
+                field.ident.span(),
+                "deserialize can not be used with required arguments",
+            ));
+        }
+        let required_args = Fields {
+            fields: required_args,
+        };
+        let fields = Fields { fields };
+
+        if attrs.typestate {
+            if let Some(field) = fields
+                .fields
+                .iter()
+                .chain(&required_args.fields)
+                .find(|f| f.validate.is_some())
+            {
+                return Err(syn::Error::new(
+                    field.ident.span(),
+                    "A typestate builder can not validate values",
+                ));
+            }
+            if let Some(field) = fields
+                .fields
+                .iter()
+                .find(|f| f.is_required() && f.setters.owned.is_none())
+            {
+                return Err(syn::Error::new(
+                    field.ident.span(),
+                    "A typestate builder needs an owned setter for required values",
+                ));
+            }
+            if let Some(field) = fields.fields.iter().find(|f| f.env.is_some()) {
+                return Err(syn::Error::new(
+                    field.ident.span(),
+                    "A typestate builder can not read environment variables",
+                ));
+            }
+            if attrs.set_by_name {
+                return Err(syn::Error::new(
+                    span,
+                    "A typestate builder can not set values by name",
+                ));
+            }
+        }
 
         Ok(Self {
             builder_ident,
             error_ident,
-            struct_ident,
+            struct_ident: struct_ident.clone(),
+            variant: variant.cloned(),
             fields,
-            generics,
-            copy_on_build: attrs.copy
+            required_args,
+            generics: generics.clone(),
+            tuple,
+            copy_on_build: attrs.copy,
+            collect_missing: attrs.collect_missing,
+            typestate: attrs.typestate,
+            serde,
+            deserialize,
+            set_by_name: attrs.set_by_name,
+            extend_collections: attrs.extend_collections,
+            validate: attrs.validate,
+            constructor: FunctionName::resolve(attrs.constructor, "new"),
+            builder_fn: FunctionName::resolve(attrs.builder_fn, default_builder_fn),
+            into_builder_fn: FunctionName::resolve(attrs.into_builder, "into_builder"),
+            to_builder_fn: FunctionName::resolve(attrs.to_builder, "to_builder"),
+            function: None,
+            serde_attrs: Vec::new(),
         })
     }
 }
 
-impl StructAttrs {
-    fn new(attrs: &[syn::Attribute], data_attr: &str) -> Result<Self, syn::Error> {
-        let builder_data: crate::attr::Attr = match find_attr(attrs, data_attr) {
-            Some(Ok(attr)) => attr,
-            Some(Err(e)) => {
-                return Err(e);
-            }
+impl DeriveData {
+    /// The type that is built, the struct or the return type of the function.
+    pub fn target_ty(&self, generics_without_bounds: &syn::Generics) -> proc_macro2::TokenStream {
+        match &self.function {
+            Some(Function { output, .. }) => quote::quote!(#output),
             None => {
-                return Ok(Self {
-                    builder_ident: None,
-                    error_ident: None,
-                    copy: false
-                })
+                let struct_ident = &self.struct_ident;
+                quote::quote!(#struct_ident #generics_without_bounds)
             }
+        }
+    }
+
+    /// The names of the fallible and the panicking build function. A function builder calls
+    /// the function with `try_call` and `call`.
+    pub fn build_fn_idents(&self) -> (syn::Ident, syn::Ident) {
+        let (try_build, build) = match self.function {
+            Some(_) => ("try_call", "call"),
+            None => ("try_build", "build"),
+        };
+        (
+            syn::Ident::new(try_build, proc_macro2::Span::call_site()),
+            syn::Ident::new(build, proc_macro2::Span::call_site()),
+        )
+    }
+
+    /// The first sentence of the documentation of the build functions.
+    pub fn build_description(&self) -> String {
+        match &self.function {
+            Some(Function { ident, .. }) => format!("Call {ident} with the set values."),
+            None => format!("Construct a new {} instance.", self.struct_ident),
+        }
+    }
+}
+
+impl StructAttrs {
+    fn new(builder_data: Option<&crate::attr::Attr>) -> Result<Self, syn::Error> {
+        let Some(builder_data) = builder_data else {
+            return Ok(Self::default());
         };
 
         let builder_ident = builder_data.find_field::<syn::Ident>("builder_ident");
@@ -124,17 +685,50 @@ impl StructAttrs {
             None => None,
         };
 
-        let copy = builder_data.find_field::<syn::LitBool>("copy");
-        let copy = match copy {
-            Some(Ok(syn::LitBool { value, span: _ })) => value,
-            Some(Err(e)) => return Err(e),
+        let copy = builder_data.find_bool("copy")?;
+        let collect_missing = builder_data.find_bool("collect_missing")?;
+        let typestate = builder_data.find_bool("typestate")?;
+        let serde = builder_data.find("serde")?;
+        let deserialize = builder_data.find("deserialize")?;
+        let validate = Validator::new(builder_data)?;
+        let into = builder_data.find_bool("into")?;
+        let setter = SetterOptions::new(builder_data)?;
+        let constructor = builder_data.find("constructor")?;
+        let builder_fn = builder_data.find("builder_fn")?;
+        let into_builder = builder_data.find("into_builder")?;
+        let to_builder = builder_data.find("to_builder")?;
+        let env_prefix = builder_data.find("env_prefix")?;
+        let set_by_name = builder_data.find_bool("set_by_name")?;
+        let extend_collections = match builder_data.find::<syn::Ident>("merge_collections")? {
+            Some(mode) if mode == "replace" => false,
+            Some(mode) if mode == "extend" => true,
+            Some(mode) => {
+                return Err(syn::Error::new(
+                    mode.span(),
+                    "Expected one of replace or extend",
+                ))
+            }
             None => false,
         };
 
         Ok(Self {
             builder_ident,
             error_ident,
-            copy
+            copy,
+            collect_missing,
+            typestate,
+            serde,
+            deserialize,
+            validate,
+            into,
+            setter,
+            constructor,
+            builder_fn,
+            into_builder,
+            to_builder,
+            env_prefix,
+            set_by_name,
+            extend_collections,
         })
     }
 }
@@ -142,7 +736,7 @@ impl StructAttrs {
 impl FieldAttrs {
     fn new(attrs: &[syn::Attribute], data_attr: &str) -> Result<Self, syn::Error> {
         let attr: crate::attr::Attr = match find_attr(attrs, data_attr) {
-            None => return Ok(Self { default: None }),
+            None => return Ok(Self::default()),
             Some(Ok(attr)) => attr,
             Some(Err(e)) => return Err(e),
         };
@@ -152,27 +746,314 @@ impl FieldAttrs {
             Some(Err(e)) => return Err(e),
             None => None,
         };
+        let validate = Validator::new(&attr)?;
+        let into = attr.find::<syn::LitBool>("into")?.map(|into| into.value);
+        let strip_option = attr.find("strip_option")?;
+        let each = attr.find("each")?;
+        let skip = attr.find_bool("skip")?;
+        let setter = SetterOptions::new(&attr)?;
+        let setter_name = attr.find("setter_name")?;
+        let required_arg = attr.find("required_arg")?;
+        let name = attr.find("name")?;
+        let env = attr.find("env")?;
+        let set_by_name = attr.find("set_by_name")?;
+
+        Ok(Self {
+            default,
+            validate,
+            into,
+            strip_option,
+            each,
+            skip,
+            setter,
+            setter_name,
+            required_arg,
+            name,
+            env,
+            set_by_name,
+        })
+    }
+}
+
+impl FunctionName {
+    fn resolve(name: Option<Self>, default: &str) -> Option<syn::Ident> {
+        match name {
+            Some(Self::Ident(ident)) => Some(ident),
+            Some(Self::Enabled(false)) => None,
+            Some(Self::Enabled(true)) | None => {
+                Some(syn::Ident::new(default, proc_macro2::Span::call_site()))
+            }
+        }
+    }
+}
+
+impl syn::parse::Parse for FunctionName {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitBool) {
+            let enabled: syn::LitBool = input.parse()?;
+            Ok(Self::Enabled(enabled.value))
+        } else {
+            input.parse().map(Self::Ident)
+        }
+    }
+}
+
+impl EnvVar {
+    fn span(&self) -> proc_macro2::Span {
+        match self {
+            Self::Name(name) => name.span(),
+            Self::Enabled(enabled) => enabled.span,
+        }
+    }
+}
 
-        Ok(Self { default })
+impl syn::parse::Parse for EnvVar {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        if input.peek(syn::LitBool) {
+            input.parse().map(Self::Enabled)
+        } else {
+            input.parse().map(Self::Name)
+        }
+    }
+}
+
+impl SetterOptions {
+    fn new(attr: &crate::attr::Attr) -> Result<Self, syn::Error> {
+        let style = match attr.find::<syn::Ident>("setter")? {
+            Some(style) if style == "owned" => Some(SetterStyle::Owned),
+            Some(style) if style == "mutable" => Some(SetterStyle::Mutable),
+            Some(style) if style == "both" => Some(SetterStyle::Both),
+            Some(style) => {
+                return Err(syn::Error::new(
+                    style.span(),
+                    "Expected one of owned, mutable or both",
+                ))
+            }
+            None => None,
+        };
+        Ok(Self {
+            style,
+            prefix: attr.find("prefix")?,
+            mut_prefix: attr.find("mut_prefix")?,
+        })
+    }
+}
+
+impl Setters {
+    /// Resolves the setter names of `name` from the field options, the struct options and the
+    /// defaults `with_` and `set_`.
+    fn new(
+        name: &syn::Ident,
+        field: &SetterOptions,
+        struct_options: &SetterOptions,
+    ) -> Result<Self, syn::Error> {
+        let style = field
+            .style
+            .or(struct_options.style)
+            .unwrap_or(SetterStyle::Both);
+        let prefix = field.prefix.as_ref().or(struct_options.prefix.as_ref());
+        let mut_prefix = field.mut_prefix.as_ref().or(struct_options.mut_prefix.as_ref());
+        let owned = match style {
+            SetterStyle::Owned | SetterStyle::Both => Some(prefixed_ident(prefix, "with_", name)?),
+            SetterStyle::Mutable => None,
+        };
+        let mutable = match style {
+            SetterStyle::Mutable | SetterStyle::Both => {
+                Some(prefixed_ident(mut_prefix, "set_", name)?)
+            }
+            SetterStyle::Owned => None,
+        };
+        if let (Some(owned), Some(mutable)) = (&owned, &mutable) {
+            if owned == mutable {
+                return Err(syn::Error::new(
+                    name.span(),
+                    format!("The owned and the mutable setter are both named {owned}"),
+                ));
+            }
+        }
+        Ok(Self { owned, mutable })
+    }
+}
+
+fn prefixed_ident(
+    prefix: Option<&syn::LitStr>,
+    default_prefix: &str,
+    name: &syn::Ident,
+) -> Result<syn::Ident, syn::Error> {
+    let prefix = prefix.map(syn::LitStr::value);
+    let prefix = prefix.as_deref().unwrap_or(default_prefix);
+    if prefix.is_empty() {
+        return Ok(name.clone());
+    }
+    syn::parse_str::<syn::Ident>(format!("{prefix}{}", name.unraw()).as_str())
+        .map(|ident| syn::Ident::new(ident.to_string().as_str(), name.span()))
+        .map_err(|_| syn::Error::new(name.span(), format!("{prefix} is not a valid prefix")))
+}
+
+impl Validator {
+    fn new(attr: &crate::attr::Attr) -> Result<Option<Self>, syn::Error> {
+        let Some(path) = attr.find::<syn::Path>("validate")? else {
+            return Ok(None);
+        };
+        let error = attr
+            .find::<syn::Type>("validate_error")?
+            .unwrap_or_else(|| syn::parse_quote!(String));
+        Ok(Some(Self { path, error }))
+    }
+}
+
+impl Field {
+    fn new(
+        member: syn::Member,
+        field: &syn::Field,
+        struct_attrs: &StructAttrs,
+        data_attr: &str,
+    ) -> Result<Self, syn::Error> {
+        let attrs = FieldAttrs::new(field.attrs.as_slice(), data_attr)?;
+        let ident = match (&member, attrs.name.clone()) {
+            (syn::Member::Named(_), Some(name)) => {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "name can only be used on tuple struct fields",
+                ))
+            }
+            (syn::Member::Named(ident), None) => ident.clone(),
+            (syn::Member::Unnamed(_), Some(name)) => name,
+            (syn::Member::Unnamed(index), None) => field.ident.clone().unwrap_or_else(|| {
+                syn::Ident::new(
+                    format!("field{}", index.index).as_str(),
+                    proc_macro2::Span::call_site(),
+                )
+            }),
+        };
+        let ty = field.ty.clone();
+        let is_optional = is_optional(&ty);
+        let collection = collection_item(is_optional.as_ref().unwrap_or(&ty)).is_some();
+        let strip_option = match attrs.strip_option {
+            Some(strip_option) if is_optional.is_none() => {
+                return Err(syn::Error::new(
+                    strip_option.span,
+                    "strip_option can only be used on Option fields",
+                ))
+            }
+            Some(strip_option) => strip_option.value,
+            None => true,
+        };
+        let required_arg = match attrs.required_arg {
+            Some(required_arg) if required_arg.value && (attrs.default.is_some() || attrs.skip) => {
+                return Err(syn::Error::new(
+                    required_arg.span,
+                    "A required argument can not have a default value",
+                ))
+            }
+            Some(required_arg) => required_arg.value,
+            None => false,
+        };
+        let skip = attrs.skip || is_phantom_data(&field.ty);
+        let default_env = || {
+            let prefix = struct_attrs.env_prefix.as_ref().map(syn::LitStr::value);
+            format!(
+                "{}{}",
+                prefix.unwrap_or_default(),
+                ident.unraw().to_string().to_uppercase()
+            )
+        };
+        let env = match attrs.env {
+            Some(EnvVar::Enabled(syn::LitBool { value: false, .. })) => None,
+            Some(env) if skip || required_arg || collection => {
+                return Err(syn::Error::new(
+                    env.span(),
+                    "env can not be used on skipped fields, required arguments and collections",
+                ))
+            }
+            Some(EnvVar::Name(env)) => Some(env.value()),
+            Some(EnvVar::Enabled(_)) => Some(default_env()),
+            // Collections can not be parsed from a single variable.
+            None if struct_attrs.env_prefix.is_some() && !skip && !required_arg && !collection => {
+                Some(default_env())
+            }
+            None => None,
+        };
+        let setters = Setters::new(
+            attrs.setter_name.as_ref().unwrap_or(&ident),
+            &attrs.setter,
+            &struct_attrs.setter,
+        )?;
+        let each = match attrs.each {
+            Some(each_ident) => {
+                let Some(item) = collection_item(is_optional.as_ref().unwrap_or(&ty)) else {
+                    return Err(syn::Error::new(
+                        each_ident.span(),
+                        "each can only be used on Vec, HashSet, BTreeSet, HashMap and BTreeMap fields",
+                    ));
+                };
+                let setters = Setters {
+                    owned: setters
+                        .owned
+                        .as_ref()
+                        .map(|_| {
+                            let prefix = attrs.setter.prefix.as_ref().or(struct_attrs.setter.prefix.as_ref());
+                            prefixed_ident(prefix, "with_", &each_ident)
+                        })
+                        .transpose()?,
+                    mutable: setters
+                        .mutable
+                        .as_ref()
+                        .map(|_| {
+                            let prefix = attrs
+                                .setter
+                                .mut_prefix
+                                .as_ref()
+                                .or(struct_attrs.setter.mut_prefix.as_ref());
+                            prefixed_ident(prefix, "add_", &each_ident)
+                        })
+                        .transpose()?,
+                };
+                Some(Each {
+                    ident: each_ident,
+                    item,
+                    setters,
+                })
+            }
+            None => None,
+        };
+        Ok(Field {
+            ident,
+            member,
+            default: attrs.default,
+            ty,
+            is_optional,
+            validate: attrs.validate,
+            into: attrs.into.unwrap_or(struct_attrs.into),
+            strip_option,
+            each,
+            skip,
+            setters,
+            required_arg,
+            serde_attrs: serde_attrs(field.attrs.as_slice()),
+            env,
+            set_by_name: attrs
+                .set_by_name
+                .map(|set_by_name| set_by_name.value)
+                .unwrap_or(!skip && !required_arg && !collection),
+            docs: docs(field.attrs.as_slice()),
+            collection,
+        })
     }
 }
 
 impl Fields {
-    fn new(fields: &syn::FieldsNamed, data_attr: &str) -> Result<Self, syn::Error> {
+    fn new(
+        fields: &syn::FieldsNamed,
+        struct_attrs: &StructAttrs,
+        data_attr: &str,
+    ) -> Result<Self, syn::Error> {
         let fields = fields
             .named
             .iter()
             .map(|field| {
-                let attrs = FieldAttrs::new(field.attrs.as_slice(), data_attr)?;
-                let ident = field.ident.clone().unwrap();
-                let ty = field.ty.clone();
-                let is_optional = is_optional(&ty);
-                Ok(Field {
-                    ident,
-                    default: attrs.default,
-                    ty,
-                    is_optional,
-                })
+                let member = syn::Member::Named(field.ident.clone().unwrap());
+                Field::new(member, field, struct_attrs, data_attr)
             })
             .collect::<Result<Vec<_>, syn::Error>>()?;
         Ok(Fields { fields })
@@ -180,23 +1061,18 @@ impl Fields {
 }
 
 impl Fields {
-    fn new_unnamed(fields: &syn::FieldsUnnamed, data_attr: &str) -> Result<Self, syn::Error> {
+    fn new_unnamed(
+        fields: &syn::FieldsUnnamed,
+        struct_attrs: &StructAttrs,
+        data_attr: &str,
+    ) -> Result<Self, syn::Error> {
         let fields = fields
             .unnamed
             .iter()
             .enumerate()
             .map(|(index, field)| {
-                let attrs = FieldAttrs::new(field.attrs.as_slice(), data_attr)?;
-                let ident =
-                    syn::Ident::new(index.to_string().as_str(), proc_macro2::Span::call_site());
-                let ty = field.ty.clone();
-                let is_optional = is_optional(&ty);
-                Ok(Field {
-                    ident,
-                    default: attrs.default,
-                    ty,
-                    is_optional,
-                })
+                let member = syn::Member::Unnamed(syn::Index::from(index));
+                Field::new(member, field, struct_attrs, data_attr)
             })
             .collect::<Result<Vec<Field>, syn::Error>>()?;
         Ok(Fields { fields })
@@ -234,8 +1110,156 @@ fn is_optional(ty: &syn::Type) -> Option<syn::Type> {
     }
 }
 
-fn find_attr<'a, T: syn::parse::Parse>(
-    attrs: &'a [syn::Attribute],
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "PhantomData")
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+fn collection_item(ty: &syn::Type) -> Option<CollectionItem> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    let mut types = arguments.args.iter().filter_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    });
+    match segment.ident.to_string().as_str() {
+        "Vec" | "HashSet" | "BTreeSet" => Some(CollectionItem::Value(Box::new(types.next()?))),
+        "HashMap" | "BTreeMap" => Some(CollectionItem::KeyValue(
+            Box::new(types.next()?),
+            Box::new(types.next()?),
+        )),
+        _ => None,
+    }
+}
+
+/// Joins the lines of the doc comments.
+fn docs(attrs: &[syn::Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn serde_attrs(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .cloned()
+        .collect()
+}
+
+/// Replaces elided lifetimes in `ty` with `lifetime`. Returns if a lifetime was replaced.
+fn name_elided_lifetimes(ty: &mut syn::Type, lifetime: &syn::Lifetime) -> bool {
+    let name = |l: &mut syn::Lifetime| {
+        if l.ident == "_" {
+            *l = lifetime.clone();
+            true
+        } else {
+            false
+        }
+    };
+    let mut named = false;
+    match ty {
+        syn::Type::Reference(reference) => {
+            match &mut reference.lifetime {
+                Some(l) => named |= name(l),
+                None => {
+                    reference.lifetime = Some(lifetime.clone());
+                    named = true;
+                }
+            }
+            named |= name_elided_lifetimes(&mut reference.elem, lifetime);
+        }
+        syn::Type::Path(syn::TypePath { qself, path }) => {
+            if let Some(qself) = qself {
+                named |= name_elided_lifetimes(&mut qself.ty, lifetime);
+            }
+            for segment in path.segments.iter_mut() {
+                let syn::PathArguments::AngleBracketed(arguments) = &mut segment.arguments else {
+                    continue;
+                };
+                for argument in arguments.args.iter_mut() {
+                    match argument {
+                        syn::GenericArgument::Lifetime(l) => named |= name(l),
+                        syn::GenericArgument::Type(ty) => {
+                            named |= name_elided_lifetimes(ty, lifetime)
+                        }
+                        syn::GenericArgument::Binding(binding) => {
+                            named |= name_elided_lifetimes(&mut binding.ty, lifetime)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        syn::Type::TraitObject(object) => {
+            for bound in object.bounds.iter_mut() {
+                if let syn::TypeParamBound::Lifetime(l) = bound {
+                    named |= name(l);
+                }
+            }
+        }
+        syn::Type::Tuple(tuple) => {
+            for ty in tuple.elems.iter_mut() {
+                named |= name_elided_lifetimes(ty, lifetime);
+            }
+        }
+        syn::Type::Slice(syn::TypeSlice { elem, .. })
+        | syn::Type::Array(syn::TypeArray { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. })
+        | syn::Type::Ptr(syn::TypePtr { elem, .. }) => {
+            named |= name_elided_lifetimes(elem, lifetime);
+        }
+        _ => {}
+    }
+    named
+}
+
+/// Replaces every `Self` in `tokens` with `self_ty`.
+fn replace_self_in_tokens(
+    tokens: proc_macro2::TokenStream,
+    self_ty: &syn::Type,
+) -> proc_macro2::TokenStream {
+    use quote::ToTokens;
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            proc_macro2::TokenTree::Ident(ident) if ident == "Self" => self_ty.to_token_stream(),
+            proc_macro2::TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_self_in_tokens(group.stream(), self_ty),
+                );
+                replaced.set_span(group.span());
+                proc_macro2::TokenTree::Group(replaced).into()
+            }
+            tt => tt.into(),
+        })
+        .collect()
+}
+
+fn find_attr<T: syn::parse::Parse>(
+    attrs: &[syn::Attribute],
     name: &str,
 ) -> Option<Result<T, syn::Error>> {
     struct DefaultValue<T> {
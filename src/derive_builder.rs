@@ -2,25 +2,43 @@ use proc_macro2::TokenStream;
 use quote::ToTokens;
 
 use crate::{
+    builder_conversion::build_conversions,
     builder_error_enum::{build_error, build_error_impl},
+    builder_serde::build_deserialize_impl,
+    builder_set_by_name::build_set_error,
     builder_struct::{build_impl, build_struct},
+    builder_typestate::{build_typestate_impl, build_typestate_struct},
     data::DeriveData,
 };
 
 pub fn derive_builder(data: DeriveData) -> syn::Result<TokenStream> {
     let mut result = proc_macro2::TokenStream::new();
-    build_struct(&data.builder_ident, data.fields.as_ref(), &data.generics).to_tokens(&mut result);
-    build_impl(
-        &data.struct_ident,
-        &data.builder_ident,
-        data.fields.as_ref(),
-        &[],
-        &data.error_ident,
-        data.copy_on_build,
-        data.generics
-    )
-    .to_tokens(&mut result);
-    build_error(&data.fields.as_ref(), &data.error_ident).to_tokens(&mut result);
-    build_error_impl(&data.fields.as_ref(), &data.error_ident).to_tokens(&mut result);
+    if data.typestate {
+        build_typestate_struct(&data).to_tokens(&mut result);
+        build_typestate_impl(&data).to_tokens(&mut result);
+        build_conversions(&data).to_tokens(&mut result);
+        return Ok(result);
+    }
+    build_struct(&data).to_tokens(&mut result);
+    build_impl(&data, data.required_args.as_ref()).to_tokens(&mut result);
+    build_error(&data).to_tokens(&mut result);
+    build_error_impl(&data).to_tokens(&mut result);
+    build_conversions(&data).to_tokens(&mut result);
+    if data.set_by_name {
+        build_set_error(&data).to_tokens(&mut result);
+    }
+    if data.deserialize {
+        build_deserialize_impl(&data).to_tokens(&mut result);
+    }
     Ok(result)
 }
+
+pub fn derive_consuming_builder(data: DeriveData) -> syn::Result<TokenStream> {
+    if data.copy_on_build {
+        return Err(syn::Error::new(
+            data.struct_ident.span(),
+            "ConsumingBuilder always moves its values, use Builder for a copying builder",
+        ));
+    }
+    derive_builder(data)
+}
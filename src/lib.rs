@@ -3,20 +3,55 @@ use proc_macro::TokenStream;
 use syn::DeriveInput;
 
 mod attr;
+mod builder_accessors;
+mod builder_conversion;
+mod builder_env;
 mod builder_error_enum;
+mod builder_function;
+mod builder_merge;
+mod builder_reflection;
+mod builder_serde;
+mod builder_set_by_name;
 mod builder_struct;
+mod builder_typestate;
 mod data;
 mod derive_builder;
 mod syn_attribute_helper;
 
-#[proc_macro_derive(Builder, attributes(builder))]
+#[proc_macro_derive(Builder, attributes(builder, serde))]
 pub fn derive_builder(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
-    let input = match DeriveData::new(input, "builder") {
+    let input = match DeriveData::from_input(input, "builder") {
         Ok(i) => i,
         Err(e) => return e.to_compile_error().into(),
     };
-    derive_builder::derive_builder(input)
+    input
+        .into_iter()
+        .map(derive_builder::derive_builder)
+        .collect::<syn::Result<proc_macro2::TokenStream>>()
+        .unwrap_or_else(|e| syn::Error::to_compile_error(&e))
+        .into()
+}
+
+#[proc_macro_derive(ConsumingBuilder, attributes(builder, serde))]
+pub fn derive_consuming_builder(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    let input = match DeriveData::from_input(input, "builder") {
+        Ok(i) => i,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    input
+        .into_iter()
+        .map(derive_builder::derive_consuming_builder)
+        .collect::<syn::Result<proc_macro2::TokenStream>>()
+        .unwrap_or_else(|e| syn::Error::to_compile_error(&e))
+        .into()
+}
+
+#[proc_macro_attribute]
+pub fn builder(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item = syn::parse_macro_input!(item as syn::Item);
+    builder_function::builder_attribute(attr.into(), item)
         .unwrap_or_else(|e| syn::Error::to_compile_error(&e))
         .into()
 }
@@ -35,7 +35,7 @@ fn construct_attribute_args(args: &[&str]) -> syn::PathArguments {
         return syn::PathArguments::None;
     }
     syn::PathArguments::Parenthesized(syn::ParenthesizedGenericArguments {
-        inputs: syn::punctuated::Punctuated::from_iter(args.into_iter().map(|arg| {
+        inputs: syn::punctuated::Punctuated::from_iter(args.iter().map(|arg| {
             syn::Type::Path(syn::TypePath {
                 path: syn::Path {
                     leading_colon: None,
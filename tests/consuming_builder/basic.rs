@@ -0,0 +1,25 @@
+use jbe::ConsumingBuilder;
+
+#[derive(PartialEq, Debug)]
+pub struct Token(usize);
+
+#[derive(ConsumingBuilder, PartialEq, Debug)]
+pub struct Session {
+    id: usize,
+    token: Token,
+    #[builder({
+        default: Token(0)
+    })]
+    refresh_token: Token,
+    name: Option<String>
+}
+
+fn main() {
+    let session = SessionBuilder::default().with_id(10).with_token(Token(42)).build();
+    assert_eq!(session, Session {
+        id: 10,
+        token: Token(42),
+        refresh_token: Token(0),
+        name: None
+    });
+}
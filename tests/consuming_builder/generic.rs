@@ -0,0 +1,18 @@
+use jbe::ConsumingBuilder;
+
+#[derive(PartialEq, Debug)]
+pub struct NotClone;
+
+#[derive(ConsumingBuilder, PartialEq, Debug)]
+pub struct Data<T> {
+    i: usize,
+    data: T
+}
+
+fn main() {
+    let data = DataBuilder::default().with_i(10).with_data(NotClone).try_build();
+    assert_eq!(data, Ok(Data {
+        i: 10,
+        data: NotClone
+    }));
+}
@@ -0,0 +1,74 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Config {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    timeout: Option<u64>,
+    #[builder({prefix: ""})]
+    workers: usize,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Job {
+    build: String,
+    name: String,
+    new: u8,
+    reset: u8,
+    merge: bool,
+    default: Option<u8>,
+    is_set: bool,
+    fields_set: u8,
+    missing_fields: u8,
+    try_build: u8,
+}
+
+fn main() {
+    let mut builder = ConfigBuilder::new()
+        .with_host(String::from("localhost"))
+        .with_port(8080)
+        .with_timeout(5)
+        .workers(2);
+    assert_eq!(builder.host(), Some(&String::from("localhost")));
+    assert_eq!(builder.port(), Some(&8080));
+    assert_eq!(builder.timeout(), Some(&5));
+
+    builder.clear_timeout();
+    assert_eq!(builder.timeout(), None);
+    builder.set_port_default();
+    assert_eq!(builder.port(), Some(&80));
+    builder.set_port(1);
+    let config = builder.with_port_default().build();
+    assert_eq!(config, Config {
+        host: String::from("localhost"),
+        port: 80,
+        timeout: None,
+        workers: 2,
+    });
+
+    let mut builder = ConfigBuilder::new().with_host(String::from("localhost"));
+    builder.reset();
+    assert_eq!(builder.host(), None);
+    assert_eq!(builder.try_build(), Err(ConfigBuilderError::UnsetHost));
+
+    // Getters that would have the name of another function are not generated.
+    let mut builder = JobBuilder::default()
+        .with_build(String::from("release"))
+        .with_name(String::from("ci"))
+        .with_new(1)
+        .with_reset(2)
+        .with_merge(true)
+        .with_is_set(false)
+        .with_fields_set(3)
+        .with_missing_fields(4)
+        .with_try_build(5);
+    assert_eq!(builder.name(), Some(&String::from("ci")));
+    assert!(builder.is_set("build"));
+    assert_eq!(builder.missing_fields(), Vec::<&str>::new());
+    builder.clear_build();
+    assert!(!builder.is_set("build"));
+    builder.reset();
+    assert_eq!(builder.fields_set(), Vec::<&str>::new());
+    assert_eq!(builder.try_build(), Err(JobBuilderError::UnsetBuild));
+}
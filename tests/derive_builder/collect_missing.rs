@@ -0,0 +1,61 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    collect_missing: true
+})]
+pub struct Config {
+    host: String,
+    port: u16,
+    #[builder({
+        default: 4
+    })]
+    workers: usize,
+    user: Option<String>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    collect_missing: true
+})]
+pub struct Item {
+    r#type: String
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    collect_missing: true
+})]
+pub struct Report {
+    missing: usize,
+    #[builder({default: 1})]
+    value: usize,
+    #[builder({required_arg: true})]
+    e: String
+}
+
+fn main() {
+    let error = ConfigBuilder::default().try_build().unwrap_err();
+    assert_eq!(error, ConfigBuilderError::Missing(vec!["host", "port"]));
+    assert_eq!(error.missing_fields(), vec!["host", "port"]);
+    assert_eq!(error.to_string(), "Error host, port not set");
+
+    let error = ConfigBuilder::default().with_port(80).try_build().unwrap_err();
+    assert_eq!(error.missing_fields(), vec!["host"]);
+
+    let error = ItemBuilder::default().try_build().unwrap_err();
+    assert_eq!(error.missing_fields(), vec!["type"]);
+
+    let error = ReportBuilder::default().try_build(String::new()).unwrap_err();
+    assert_eq!(error.missing_fields(), vec!["missing"]);
+    let report = ReportBuilder::default().with_missing(2).build(String::from("e"));
+    assert_eq!(report, Report { missing: 2, value: 1, e: String::from("e") });
+
+    let config = ConfigBuilder::default().with_host(String::from("localhost")).with_port(80).build();
+    assert_eq!(config, Config {
+        host: String::from("localhost"),
+        port: 80,
+        workers: 4,
+        user: None
+    });
+}
@@ -0,0 +1,57 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct User {
+    id: usize,
+    name: Option<String>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    constructor: empty,
+    builder_fn: configure
+})]
+pub struct Data<T: Clone> {
+    data: T
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    constructor: false,
+    builder_fn: false
+})]
+pub struct Config {
+    port: u16
+}
+
+impl Config {
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default().with_port(80)
+    }
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct Point {
+    x: i32,
+    y: i32
+}
+
+fn main() {
+    let user = UserBuilder::new().with_id(1).build();
+    assert_eq!(user, User { id: 1, name: None });
+    let user = User::builder().with_id(2).with_name(String::from("Jon")).build();
+    assert_eq!(user, User { id: 2, name: Some(String::from("Jon")) });
+
+    let data = DataBuilder::empty().with_data(1u8).build();
+    assert_eq!(data, Data { data: 1 });
+    let data = Data::configure().with_data(2u8).build();
+    assert_eq!(data, Data { data: 2 });
+
+    assert_eq!(Config::builder().build(), Config { port: 80 });
+
+    let point = PointBuilder::new().with_x(1).with_y(2).build();
+    assert_eq!(point, Point { x: 1, y: 2 });
+    let point = Point::builder().with_y(2).with_x(1).build();
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
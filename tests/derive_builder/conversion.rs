@@ -0,0 +1,74 @@
+use jbe::Builder;
+
+#[derive(Builder, Clone, PartialEq, Debug)]
+pub struct Config<T> {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    timeout: Option<u64>,
+    extra: T,
+    #[builder({skip: true})]
+    hits: usize,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Token(String);
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Session {
+    id: usize,
+    token: Token,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Pair(String, Option<u8>);
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct Point {
+    x: i32,
+    y: i32,
+    label: Option<String>,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({to_builder: false, into_builder: builder_from})]
+pub struct Job {
+    name: String,
+}
+
+impl Job {
+    pub fn to_builder(&self) -> JobBuilder {
+        JobBuilder::new().with_name(format!("copy of {}", self.name))
+    }
+}
+
+fn main() {
+    let config = Config {
+        host: String::from("localhost"),
+        port: 8080,
+        timeout: None,
+        extra: 1,
+        hits: 3,
+    };
+    let changed = config.to_builder().with_port(443).build();
+    assert_eq!(changed, Config { port: 443, hits: 0, ..config.clone() });
+    let rebuilt = ConfigBuilder::from(config.clone()).build();
+    assert_eq!(rebuilt, Config { hits: 0, ..config.clone() });
+    assert_eq!(config.into_builder().try_build().map(|c| c.port), Ok(8080));
+
+    let session = Session { id: 1, token: Token(String::from("secret")) };
+    let session = session.into_builder().with_id(2).build();
+    assert_eq!(session, Session { id: 2, token: Token(String::from("secret")) });
+
+    let pair = Pair(String::from("a"), Some(1));
+    assert_eq!(pair.to_builder().with_field1(2).build(), Pair(String::from("a"), Some(2)));
+
+    let point = Point { x: 1, y: 2, label: None };
+    let point = point.into_builder().with_y(3).build();
+    assert_eq!(point, Point { x: 1, y: 3, label: None });
+
+    let job = Job { name: String::from("a") };
+    assert_eq!(job.to_builder().build(), Job { name: String::from("copy of a") });
+    assert_eq!(job.builder_from().build(), Job { name: String::from("a") });
+}
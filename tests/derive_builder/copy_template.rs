@@ -0,0 +1,30 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({copy: true})]
+pub struct User {
+    id: usize,
+    name: String,
+    #[builder({
+        default: String::from("empty")
+    })]
+    email: String
+}
+
+fn main() {
+    let mut template = UserBuilder::default().with_name(String::from("Jon"));
+    template.set_id(1);
+    let first = template.build();
+    template.set_id(2);
+    let second = template.build();
+    assert_eq!(first, User {
+        id: 1,
+        name: String::from("Jon"),
+        email: String::from("empty")
+    });
+    assert_eq!(second, User {
+        id: 2,
+        name: String::from("Jon"),
+        email: String::from("empty")
+    });
+}
@@ -0,0 +1,80 @@
+use jbe::Builder;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Request {
+    url: String,
+    #[builder({each: header})]
+    headers: HashMap<String, String>,
+    #[builder({each: param})]
+    params: BTreeMap<String, usize>,
+    #[builder({each: tag, into: true})]
+    tags: HashSet<String>,
+    #[builder({each: flag})]
+    flags: BTreeSet<u8>,
+    #[builder({each: cookie})]
+    cookies: Vec<String>,
+    #[builder({each: extra})]
+    extras: Option<Vec<u8>>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({mut_prefix: "push_"})]
+pub struct Batch {
+    #[builder({each: job})]
+    jobs: Vec<String>,
+    #[builder({each: tag, mut_prefix: "insert_"})]
+    tags: BTreeSet<String>
+}
+
+fn main() {
+    let mut builder = RequestBuilder::default()
+        .with_url(String::from("https://example.com"))
+        .with_header(String::from("Accept"), String::from("*/*"))
+        .extend_params([(String::from("page"), 1), (String::from("size"), 10)])
+        .with_tag("a")
+        .with_tag("b")
+        .with_flag(2)
+        .extend_flags([1, 2, 3]);
+    builder.add_header(String::from("Host"), String::from("example.com"));
+    builder.add_cookie(String::from("session=1"));
+    let request = builder.build();
+    assert_eq!(request, Request {
+        url: String::from("https://example.com"),
+        headers: HashMap::from([
+            (String::from("Accept"), String::from("*/*")),
+            (String::from("Host"), String::from("example.com")),
+        ]),
+        params: BTreeMap::from([(String::from("page"), 1), (String::from("size"), 10)]),
+        tags: HashSet::from([String::from("a"), String::from("b")]),
+        flags: BTreeSet::from([1, 2, 3]),
+        cookies: vec![String::from("session=1")],
+        extras: None
+    });
+
+    let request = RequestBuilder::default()
+        .with_url(String::from("https://example.com"))
+        .with_cookies(vec![String::from("a=1")])
+        .with_cookie(String::from("b=2"))
+        .with_extra(1)
+        .try_build();
+    assert_eq!(request, Ok(Request {
+        url: String::from("https://example.com"),
+        headers: HashMap::new(),
+        params: BTreeMap::new(),
+        tags: HashSet::new(),
+        flags: BTreeSet::new(),
+        cookies: vec![String::from("a=1"), String::from("b=2")],
+        extras: Some(vec![1])
+    }));
+
+    assert_eq!(RequestBuilder::default().try_build(), Err(RequestBuilderError::UnsetUrl));
+
+    let mut builder = BatchBuilder::default();
+    builder.push_job(String::from("a"));
+    builder.insert_tag(String::from("b"));
+    assert_eq!(builder.build(), Batch {
+        jobs: vec![String::from("a")],
+        tags: BTreeSet::from([String::from("b")])
+    });
+}
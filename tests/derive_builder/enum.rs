@@ -0,0 +1,42 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({into: true})]
+pub enum Shape {
+    Circle {
+        radius: f64,
+        label: Option<String>,
+    },
+    #[builder({builder_ident: RectBuilder, builder_fn: rect})]
+    Rectangle(
+        #[builder({name: width})]
+        f64,
+        #[builder({name: height, default: 1.0})]
+        f64,
+    ),
+    Empty,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub enum Message<T> {
+    Send { to: String, payload: T },
+}
+
+fn main() {
+    let circle = Shape::circle_builder().with_radius(2.0).with_label("c").build();
+    assert_eq!(circle, Shape::Circle { radius: 2.0, label: Some(String::from("c")) });
+    let error = ShapeCircleBuilder::new().try_build();
+    assert_eq!(error, Err(ShapeCircleBuilderError::UnsetRadius));
+
+    let rectangle = Shape::rect().with_width(3.0).build();
+    assert_eq!(rectangle, Shape::Rectangle(3.0, 1.0));
+    let error = RectBuilder::new().try_build();
+    assert_eq!(error, Err(RectBuilderError::UnsetWidth));
+
+    let message = Message::send_builder()
+        .with_payload(5)
+        .with_to(String::from("a"))
+        .build();
+    assert_eq!(message, Message::Send { to: String::from("a"), payload: 5 });
+}
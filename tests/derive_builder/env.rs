@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({env_prefix: "APP_"})]
+pub struct Config {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    #[builder({env: "LOG_LEVEL"})]
+    log_level: Option<String>,
+    debug: Option<bool>,
+    #[builder({each: tag})]
+    tags: Vec<String>,
+    #[builder({default: HashMap::new()})]
+    headers: HashMap<String, String>,
+    #[builder({env: false})]
+    token: Option<Token>,
+    #[builder({env: true})]
+    user: Option<String>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Token(String);
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({env_prefix: "DEPLOY_"})]
+pub struct Deployment {
+    env: String,
+    replicas: u16,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Server {
+    #[builder({env: "JBE_TEST_SERVER_NAME"})]
+    name: String,
+    workers: usize,
+}
+
+fn main() {
+    let vars = HashMap::from([
+        ("APP_HOST", "localhost"),
+        ("APP_PORT", "8080"),
+        ("LOG_LEVEL", "info"),
+        ("APP_USER", "admin"),
+    ]);
+    let config = ConfigBuilder::from_env_source(|key| vars.get(key).map(ToString::to_string))
+        .unwrap()
+        .build();
+    assert_eq!(config, Config {
+        host: String::from("localhost"),
+        port: 8080,
+        log_level: Some(String::from("info")),
+        debug: None,
+        tags: Vec::new(),
+        headers: HashMap::new(),
+        token: None,
+        user: Some(String::from("admin")),
+    });
+
+    let config = ConfigBuilder::new()
+        .with_host(String::from("example.com"))
+        .with_debug(true)
+        .apply_env_source(|key| (key == "APP_PORT").then(|| String::from("443")))
+        .unwrap()
+        .build();
+    assert_eq!(config, Config {
+        host: String::from("example.com"),
+        port: 443,
+        log_level: None,
+        debug: Some(true),
+        tags: Vec::new(),
+        headers: HashMap::new(),
+        token: None,
+        user: None,
+    });
+
+    let Err(error) =
+        ConfigBuilder::from_env_source(|key| (key == "APP_PORT").then(|| String::from("port")))
+    else {
+        panic!("APP_PORT is not a number");
+    };
+    assert_eq!(error, ConfigBuilderError::ParsePort(String::from("invalid digit found in string")));
+    assert_eq!(error.to_string(), "Error port could not be parsed: invalid digit found in string");
+
+    let deployment = DeploymentBuilder::new()
+        .with_env(String::from("staging"))
+        .apply_env_source(|key| (key == "DEPLOY_REPLICAS").then(|| String::from("3")))
+        .unwrap()
+        .build();
+    assert_eq!(deployment, Deployment { env: String::from("staging"), replicas: 3 });
+
+    std::env::set_var("JBE_TEST_SERVER_NAME", "main");
+    let server = ServerBuilder::from_env().unwrap().with_workers(2).build();
+    assert_eq!(server, Server { name: String::from("main"), workers: 2 });
+}
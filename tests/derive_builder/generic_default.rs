@@ -6,6 +6,9 @@ pub struct Data<T = String> {
     data: T
 }
 
+#[derive(PartialEq, Debug)]
+pub struct NoDefault(u8);
+
 fn main() {
     let builder = DataBuilder::default();
     let user = builder.with_i(10).with_data(String::from("Jon")).build();
@@ -13,4 +16,11 @@ fn main() {
         i: 10,
         data: String::from("Jon")
     });
+
+    let builder: DataBuilder<NoDefault> = DataBuilder::default();
+    let data = builder.with_i(10).with_data(NoDefault(1)).build();
+    assert_eq!(data, Data {
+        i: 10,
+        data: NoDefault(1)
+    });
 }
\ No newline at end of file
@@ -9,6 +9,9 @@ pub struct Data<T>
     data: T
 }
 
+#[derive(Clone, PartialEq, Debug)]
+pub struct NoDefault(u8);
+
 fn main() {
     let builder = DataBuilder::default();
     let user = builder.with_i(10).with_data(String::from("Jon")).build();
@@ -16,4 +19,15 @@ fn main() {
         i: 10,
         data: String::from("Jon")
     });
+
+    let data = DataBuilder::<NoDefault>::new().with_i(10).with_data(NoDefault(1)).build();
+    assert_eq!(data, Data {
+        i: 10,
+        data: NoDefault(1)
+    });
+    let data = Data::<NoDefault>::builder().with_i(10).with_data(NoDefault(2)).build();
+    assert_eq!(data, Data {
+        i: 10,
+        data: NoDefault(2)
+    });
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Frame<'a, const N: usize> {
+    data: &'a [u8; N],
+    id: usize
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({copy: true})]
+pub struct Pair<'a, 'b: 'a, T: 'a + Clone> {
+    first: &'a T,
+    second: &'b str,
+    label: Option<&'a str>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Buffer<'a, T, const N: usize = 4>
+    where T: 'a
+{
+    items: &'a [T; N],
+    #[builder({default: N})]
+    len: usize
+}
+
+fn main() {
+    let data = [1, 2, 3];
+    let frame = FrameBuilder::new().with_data(&data).with_id(1).build();
+    assert_eq!(frame, Frame { data: &data, id: 1 });
+
+    let value = 5;
+    let second = String::from("second");
+    let builder = PairBuilder::new().with_first(&value).with_second(second.as_str());
+    let pair = builder.build();
+    assert_eq!(pair, Pair { first: &5, second: "second", label: None });
+
+    let items = [1u8; 4];
+    let buffer: Buffer<u8> = Buffer::builder().with_items(&items).build();
+    assert_eq!(buffer, Buffer { items: &items, len: 4 });
+}
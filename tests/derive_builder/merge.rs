@@ -0,0 +1,62 @@
+use jbe::Builder;
+
+#[derive(Builder, Clone, PartialEq, Debug)]
+pub struct Config {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    timeout: Option<u64>,
+    tags: Vec<String>,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({merge_collections: extend})]
+pub struct Layered {
+    name: String,
+    paths: Vec<String>,
+}
+
+fn main() {
+    let defaults = ConfigBuilder::default()
+        .with_host(String::from("localhost"))
+        .with_port(8080)
+        .with_tags(vec![String::from("default")]);
+    let user = ConfigBuilder::default()
+        .with_port(443)
+        .with_tags(vec![String::from("user")]);
+    let config = defaults.merge(user).build();
+    assert_eq!(
+        config,
+        Config {
+            host: String::from("localhost"),
+            port: 443,
+            timeout: None,
+            tags: vec![String::from("user")],
+        }
+    );
+
+    let base = Config {
+        host: String::from("example.com"),
+        port: 1,
+        timeout: Some(10),
+        tags: Vec::new(),
+    };
+    let filled = ConfigBuilder::default()
+        .with_port(2)
+        .fill_missing_from(&base)
+        .build();
+    assert_eq!(filled, Config { port: 2, ..base.clone() });
+
+    let layered = LayeredBuilder::default()
+        .with_name(String::from("system"))
+        .with_paths(vec![String::from("/etc")])
+        .merge(LayeredBuilder::default().with_paths(vec![String::from("~/.config")]))
+        .build();
+    assert_eq!(
+        layered,
+        Layered {
+            name: String::from("system"),
+            paths: vec![String::from("/etc"), String::from("~/.config")],
+        }
+    );
+}
@@ -0,0 +1,40 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct User {
+    id: usize,
+    email: Option<String>,
+    #[builder({strip_option: false})]
+    nickname: Option<String>,
+    #[builder({into: true})]
+    phone: Option<String>
+}
+
+fn main() {
+    let email = Some(String::from("jon@example.com"));
+    let builder = UserBuilder::default()
+        .with_id(10)
+        .maybe_with_email(email)
+        .with_nickname(Some(String::from("J")))
+        .maybe_with_phone(Some("0123"));
+    assert_eq!(builder.try_build(), Ok(User {
+        id: 10,
+        email: Some(String::from("jon@example.com")),
+        nickname: Some(String::from("J")),
+        phone: Some(String::from("0123"))
+    }));
+
+    let mut builder = UserBuilder::default()
+        .with_id(10)
+        .with_email(String::from("jon@example.com"))
+        .with_phone("0123");
+    builder.set_email_opt(None);
+    builder.set_nickname(None);
+    builder.set_phone_opt(None::<&str>);
+    assert_eq!(builder.build(), User {
+        id: 10,
+        email: None,
+        nickname: None,
+        phone: None
+    });
+}
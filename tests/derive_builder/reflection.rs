@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Config {
+    /// The host name of the server.
+    host: String,
+    /// The port of the server.
+    /// Defaults to 80.
+    #[builder({default: 80})]
+    port: u16,
+    timeout: Option<u64>,
+    labels: HashMap<String, Vec<String>>,
+    #[builder({skip: true})]
+    cache: Vec<u8>,
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Form {
+    name: String,
+    #[builder({each: field})]
+    fields: Vec<String>,
+}
+
+fn required_fields(fields: &[(&'static str, &str, &str, &str)]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter(|(_, _, status, _)| *status == "required")
+        .map(|(name, ..)| *name)
+        .collect()
+}
+
+fn main() {
+    assert_eq!(ConfigBuilder::FIELDS, &[
+        ("host", "String", "required", "The host name of the server."),
+        ("port", "u16", "default", "The port of the server.\nDefaults to 80."),
+        ("timeout", "Option<u64>", "optional", ""),
+        ("labels", "HashMap<String, Vec<String>>", "required", ""),
+    ]);
+    assert_eq!(required_fields(ConfigBuilder::FIELDS), vec!["host", "labels"]);
+    assert_eq!(required_fields(FormBuilder::FIELDS), vec!["name"]);
+
+    let builder = ConfigBuilder::new().with_host(String::from("localhost")).with_timeout(5);
+    assert!(builder.is_set("host"));
+    assert!(!builder.is_set("port"));
+    assert!(!builder.is_set("cache"));
+    assert_eq!(builder.fields_set(), vec!["host", "timeout"]);
+    assert_eq!(builder.missing_fields(), vec!["labels"]);
+
+    let mut form = FormBuilder::new().with_name(String::from("login"));
+    form.set_fields(vec![String::from("user")]);
+    assert_eq!(form.fields_set(), vec!["name", "fields"]);
+}
@@ -14,4 +14,5 @@ fn main() {
     let builder = UserBuilder::default();
     let user = builder.with_id(10).with_name(String::from("Jon")).try_build();
     assert_eq!(user, Err(TestError::UnsetEmail));
+    assert_eq!(TestError::UnsetEmail.missing_fields(), vec!["email"]);
 }
\ No newline at end of file
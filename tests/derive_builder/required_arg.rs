@@ -0,0 +1,45 @@
+use jbe::Builder;
+
+fn not_empty(value: &String) -> Result<(), String> {
+    if value.is_empty() {
+        Err(String::from("value is empty"))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Connection {
+    #[builder({required_arg: true})]
+    host: String,
+    #[builder({required_arg: true, into: true, validate: not_empty})]
+    user: String,
+    port: u16,
+    timeout: Option<u64>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct Point {
+    #[builder({required_arg: true})]
+    x: i32,
+    y: i32
+}
+
+fn main() {
+    let connection = ConnectionBuilder::new().with_port(22).build(String::from("localhost"), "root");
+    assert_eq!(connection, Connection {
+        host: String::from("localhost"),
+        user: String::from("root"),
+        port: 22,
+        timeout: None
+    });
+
+    let error = ConnectionBuilder::new().try_build(String::from("localhost"), "root");
+    assert_eq!(error, Err(ConnectionBuilderError::UnsetPort));
+    let error = ConnectionBuilder::new().with_port(22).try_build(String::from("localhost"), "");
+    assert_eq!(error, Err(ConnectionBuilderError::InvalidUser(String::from("value is empty"))));
+
+    let point = PointBuilder::new().with_y(2).build(1);
+    assert_eq!(point, Point { x: 1, y: 2 });
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({set_by_name: true})]
+pub struct Config<T> {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    timeout: Option<T>,
+    tags: Vec<String>,
+    #[builder({each: header})]
+    headers: HashMap<String, String>,
+    #[builder({set_by_name: false})]
+    token: Option<Token>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Token(String);
+
+fn main() {
+    let mut builder = ConfigBuilder::<u64>::new().with_tags(Vec::new());
+    builder.set_by_name("host", "localhost").unwrap();
+    builder.set_by_name("timeout", "5").unwrap();
+    assert_eq!(builder.build(), Config {
+        host: String::from("localhost"),
+        port: 80,
+        timeout: Some(5),
+        tags: Vec::new(),
+        headers: HashMap::new(),
+        token: None,
+    });
+
+    let mut builder = ConfigBuilder::<u64>::new();
+    assert_eq!(
+        builder.set_by_name("tags", "a"),
+        Err(ConfigBuilderSetError::UnknownField(String::from("tags")))
+    );
+    assert_eq!(
+        builder.set_by_name("token", "a"),
+        Err(ConfigBuilderSetError::UnknownField(String::from("token")))
+    );
+    let error = builder.set_by_name("port", "http").unwrap_err();
+    assert_eq!(error, ConfigBuilderSetError::Parse {
+        field: "port",
+        message: String::from("invalid digit found in string"),
+    });
+    assert_eq!(error.to_string(), "Error port could not be parsed: invalid digit found in string");
+
+    let overrides = HashMap::from([("host", "example.com"), ("port", "8080")]);
+    let config = ConfigBuilder::<u64>::from_pairs(overrides)
+        .unwrap()
+        .with_tags(vec![String::from("a")])
+        .build();
+    assert_eq!(config, Config {
+        host: String::from("example.com"),
+        port: 8080,
+        timeout: None,
+        tags: vec![String::from("a")],
+        headers: HashMap::new(),
+        token: None,
+    });
+    let error = ConfigBuilder::<u64>::from_pairs([(String::from("hots"), String::from("a"))]);
+    assert_eq!(error.err(), Some(ConfigBuilderSetError::UnknownField(String::from("hots"))));
+}
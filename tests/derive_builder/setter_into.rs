@@ -0,0 +1,33 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({into: true})]
+pub struct User {
+    #[builder({into: false})]
+    id: u64,
+    name: String,
+    email: Option<String>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Server {
+    #[builder({into: true})]
+    host: String,
+    port: u16
+}
+
+fn main() {
+    let mut builder = UserBuilder::default().with_id(10).with_name("Jon");
+    builder.set_email("jon@example.com");
+    assert_eq!(builder.build(), User {
+        id: 10,
+        name: String::from("Jon"),
+        email: Some(String::from("jon@example.com"))
+    });
+
+    let server = ServerBuilder::default().with_host("localhost").with_port(80).build();
+    assert_eq!(server, Server {
+        host: String::from("localhost"),
+        port: 80
+    });
+}
@@ -0,0 +1,56 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({setter: owned, prefix: ""})]
+pub struct Point {
+    x: i32,
+    #[builder({setter_name: vertical})]
+    y: i32,
+    #[builder({prefix: "and_"})]
+    label: Option<String>,
+    #[builder({setter: both, mut_prefix: "put_"})]
+    r#type: u8,
+    #[builder({each: tag})]
+    tags: Vec<String>
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({setter: mutable})]
+pub struct Counter {
+    count: usize,
+    #[builder({setter: owned})]
+    step: usize,
+    #[builder({each: entry})]
+    entries: Vec<usize>,
+    name: Option<String>
+}
+
+fn main() {
+    let mut builder = PointBuilder::default()
+        .x(1)
+        .vertical(2)
+        .and_label(String::from("a"))
+        .maybe_and_label(None)
+        .tag(String::from("t"))
+        .extend_tags([String::from("u")]);
+    builder.put_type(3);
+    assert_eq!(builder.build(), Point {
+        x: 1,
+        y: 2,
+        label: None,
+        r#type: 3,
+        tags: vec![String::from("t"), String::from("u")]
+    });
+
+    let mut builder = CounterBuilder::default().with_step(2);
+    builder.set_count(1);
+    builder.add_entry(1);
+    builder.extend_entries([2, 3]);
+    builder.set_name_opt(Some(String::from("counter")));
+    assert_eq!(builder.build(), Counter {
+        count: 1,
+        step: 2,
+        entries: vec![1, 2, 3],
+        name: Some(String::from("counter"))
+    });
+}
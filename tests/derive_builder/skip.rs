@@ -0,0 +1,38 @@
+use jbe::Builder;
+use std::marker::PhantomData;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Cache {
+    name: String,
+    #[builder({skip: true})]
+    hits: usize,
+    #[builder({
+        skip: true,
+        default: vec![0; 4]
+    })]
+    slots: Vec<u8>
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Meters;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Length<Unit> {
+    value: f64,
+    unit: PhantomData<Unit>
+}
+
+fn main() {
+    let cache = CacheBuilder::default().with_name(String::from("cache")).build();
+    assert_eq!(cache, Cache {
+        name: String::from("cache"),
+        hits: 0,
+        slots: vec![0; 4]
+    });
+
+    let length: Length<Meters> = LengthBuilder::default().with_value(1.5).build();
+    assert_eq!(length, Length {
+        value: 1.5,
+        unit: PhantomData
+    });
+}
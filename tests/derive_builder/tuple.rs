@@ -0,0 +1,44 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Rgb(
+    #[builder({name: red})]
+    u8,
+    #[builder({name: green})]
+    u8,
+    #[builder({name: blue, default: 0})]
+    u8,
+);
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Pair(String, Option<usize>);
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Labeled(
+    #[builder({required_arg: true})]
+    String,
+    u32,
+);
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Unit;
+
+fn main() {
+    let color = RgbBuilder::new().with_green(128).with_red(255).build();
+    assert_eq!(color, Rgb(255, 128, 0));
+    let error = RgbBuilder::new().with_red(255).try_build();
+    assert_eq!(error, Err(RgbBuilderError::UnsetGreen));
+
+    let mut builder = PairBuilder::new();
+    builder.set_field0(String::from("a"));
+    let pair = builder.with_field1(1).build();
+    assert_eq!(pair, Pair(String::from("a"), Some(1)));
+    let error = PairBuilder::new().try_build();
+    assert_eq!(error, Err(PairBuilderError::UnsetField0));
+    assert_eq!(error.unwrap_err().to_string(), "Error field0 not set");
+
+    let labeled = LabeledBuilder::new().with_field1(3).build(String::from("x"));
+    assert_eq!(labeled, Labeled(String::from("x"), 3));
+
+    assert_eq!(UnitBuilder::new().build(), Unit);
+}
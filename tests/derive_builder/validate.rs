@@ -0,0 +1,107 @@
+use jbe::Builder;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct PortError(u16);
+
+impl std::fmt::Display for PortError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is not a valid port", self.0)
+    }
+}
+
+fn check_port(port: &u16) -> Result<(), PortError> {
+    if *port == 0 {
+        Err(PortError(*port))
+    } else {
+        Ok(())
+    }
+}
+
+mod checks {
+    pub fn not_empty(value: &String) -> Result<(), String> {
+        if value.is_empty() {
+            Err(String::from("value is empty"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NameError;
+
+impl std::fmt::Display for NameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "name is reserved")
+    }
+}
+
+fn check_name(name: &String) -> Result<(), NameError> {
+    if name == "root" {
+        Err(NameError)
+    } else {
+        Ok(())
+    }
+}
+
+fn check_path(path: &String) -> Result<(), Box<dyn std::error::Error>> {
+    std::path::Path::new(path).canonicalize()?;
+    Ok(())
+}
+
+#[derive(Builder, Debug)]
+pub struct Account {
+    #[builder({validate: check_name, validate_error: NameError})]
+    name: String,
+    #[builder({validate: check_path, validate_error: Box<dyn std::error::Error>})]
+    home: String
+}
+
+#[derive(Builder, PartialEq, Debug)]
+pub struct Server {
+    #[builder({validate: checks::not_empty})]
+    name: String,
+    #[builder({
+        validate: check_port,
+        validate_error: PortError
+    })]
+    port: u16,
+    #[builder({validate: checks::not_empty, default: String::from("localhost")})]
+    host: String,
+    #[builder({validate: checks::not_empty})]
+    user: Option<String>
+}
+
+fn main() {
+    let error = ServerBuilder::default().with_name(String::new()).with_port(80).try_build();
+    assert_eq!(error, Err(ServerBuilderError::InvalidName(String::from("value is empty"))));
+    assert_eq!(error.unwrap_err().to_string(), "Error name invalid: value is empty");
+
+    let error = ServerBuilder::default().with_name(String::from("web")).with_port(0).try_build();
+    assert_eq!(error, Err(ServerBuilderError::InvalidPort(PortError(0))));
+    assert_eq!(error.unwrap_err().to_string(), "Error port invalid: 0 is not a valid port");
+
+    let error = ServerBuilder::default().with_name(String::from("web")).with_port(80).with_host(String::new()).try_build();
+    assert_eq!(error, Err(ServerBuilderError::InvalidHost(String::from("value is empty"))));
+
+    let error = ServerBuilder::default().with_name(String::from("web")).with_port(80).with_user(String::new()).try_build();
+    assert_eq!(error, Err(ServerBuilderError::InvalidUser(String::from("value is empty"))));
+
+    let error = ServerBuilder::default().with_name(String::from("web")).try_build();
+    assert_eq!(error, Err(ServerBuilderError::UnsetPort));
+
+    let error = AccountBuilder::default().with_name(String::from("root")).with_home(String::from(".")).try_build();
+    assert!(matches!(error, Err(AccountBuilderError::InvalidName(NameError))));
+    let error = AccountBuilder::default().with_name(String::from("jon")).with_home(String::from("/does/not/exist")).try_build();
+    assert!(matches!(error, Err(AccountBuilderError::InvalidHome(_))));
+    let error: Box<dyn std::error::Error> = Box::new(AccountBuilderError::UnsetName);
+    assert_eq!(error.to_string(), "Error name not set");
+
+    let server = ServerBuilder::default().with_name(String::from("web")).with_port(80).build();
+    assert_eq!(server, Server {
+        name: String::from("web"),
+        port: 80,
+        host: String::from("localhost"),
+        user: None
+    });
+}
@@ -0,0 +1,66 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({validate: Self::check})]
+pub struct Range<T: PartialOrd> {
+    min: T,
+    max: T
+}
+
+impl<T: PartialOrd> Range<T> {
+    fn check(&self) -> Result<(), String> {
+        if self.min <= self.max {
+            Ok(())
+        } else {
+            Err(String::from("min is greater than max"))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TlsError {
+    CertWithoutKey,
+    KeyWithoutCert,
+}
+
+impl std::fmt::Display for TlsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CertWithoutKey => write!(f, "cert is set without a key"),
+            Self::KeyWithoutCert => write!(f, "key is set without a cert"),
+        }
+    }
+}
+
+fn check_tls(tls: &Tls) -> Result<(), TlsError> {
+    match (&tls.cert, &tls.key) {
+        (Some(_), None) => Err(TlsError::CertWithoutKey),
+        (None, Some(_)) => Err(TlsError::KeyWithoutCert),
+        _ => Ok(()),
+    }
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({
+    validate: check_tls,
+    validate_error: TlsError
+})]
+pub struct Tls {
+    cert: Option<String>,
+    key: Option<String>
+}
+
+fn main() {
+    let range = RangeBuilder::default().with_min(1).with_max(2).build();
+    assert_eq!(range, Range { min: 1, max: 2 });
+    let error = RangeBuilder::default().with_min(3).with_max(2).try_build();
+    assert_eq!(error, Err(RangeBuilderError::Validation(String::from("min is greater than max"))));
+    assert_eq!(error.unwrap_err().to_string(), "Error Range invalid: min is greater than max");
+    let panic = std::panic::catch_unwind(|| RangeBuilder::default().with_min(3).with_max(2).build()).unwrap_err();
+    assert_eq!(panic.downcast_ref::<String>().unwrap(), "Error Range invalid: min is greater than max");
+
+    let error = TlsBuilder::default().with_cert(String::from("cert")).try_build();
+    assert_eq!(error, Err(TlsBuilderError::Validation(TlsError::CertWithoutKey)));
+    let tls = TlsBuilder::default().build();
+    assert_eq!(tls, Tls { cert: None, key: None });
+}
@@ -0,0 +1,10 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+#[builder({builder_ident: ShapeBuilder})]
+pub enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+pub struct Config {
+    host: String,
+    #[builder({env: "APP_PATHS"})]
+    paths: Vec<String>
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+pub struct Connection {
+    #[builder({required_arg: true})]
+    host: String,
+    port: u16
+}
+
+fn main() {
+    let _connection = ConnectionBuilder::new().with_host(String::from("localhost")).with_port(22).build();
+}
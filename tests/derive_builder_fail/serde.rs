@@ -0,0 +1,9 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+#[builder({serde: true})]
+pub struct Config {
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+#[builder({prefix: "", mut_prefix: ""})]
+pub struct Point {
+    x: i32
+}
+
+fn main() {}
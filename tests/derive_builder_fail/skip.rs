@@ -0,0 +1,12 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+pub struct Cache {
+    name: String,
+    #[builder({skip: true})]
+    hits: usize
+}
+
+fn main() {
+    let _cache = CacheBuilder::default().with_name(String::from("cache")).with_hits(1).build();
+}
@@ -0,0 +1,10 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+pub struct Point {
+    #[builder({name: horizontal})]
+    x: i32,
+    y: i32,
+}
+
+fn main() {}
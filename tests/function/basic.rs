@@ -0,0 +1,64 @@
+#[derive(PartialEq, Debug)]
+pub struct Connection {
+    host: String,
+    port: u16,
+    timeout: Option<u64>,
+}
+
+#[jbe::builder]
+fn connect(
+    host: String,
+    #[builder({default: 80})] port: u16,
+    timeout: Option<u64>,
+) -> Connection {
+    Connection { host, port, timeout }
+}
+
+#[jbe::builder({collect_missing: true})]
+fn sum(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[jbe::builder]
+fn greet(name: &str, times: Option<u8>) -> String {
+    name.repeat(times.unwrap_or(1) as usize)
+}
+
+#[jbe::builder]
+fn word(text: &str, index: Option<usize>) -> &str {
+    text.split(' ').nth(index.unwrap_or_default()).unwrap_or_default()
+}
+
+#[jbe::builder]
+fn label(name: std::borrow::Cow<'_, str>, suffix: Option<&'static str>) -> String {
+    format!("{name}{}", suffix.unwrap_or_default())
+}
+
+fn main() {
+    let connection = ConnectBuilder::new()
+        .with_host(String::from("localhost"))
+        .call();
+    assert_eq!(connection, Connection {
+        host: String::from("localhost"),
+        port: 80,
+        timeout: None,
+    });
+    let connection = ConnectBuilder::new()
+        .with_host(String::from("localhost"))
+        .with_port(22)
+        .with_timeout(5)
+        .call();
+    assert_eq!(connection, connect(String::from("localhost"), 22, Some(5)));
+
+    let error = ConnectBuilder::new().try_call();
+    assert_eq!(error, Err(ConnectBuilderError::UnsetHost));
+
+    assert_eq!(SumBuilder::new().with_b(1).with_a(2).call(), 3);
+    assert_eq!(SumBuilder::new().try_call(), Err(SumBuilderError::Missing(vec!["a", "b"])));
+
+    assert_eq!(GreetBuilder::new().with_name("hi").with_times(2).call(), "hihi");
+    let text = String::from("hello world");
+    assert_eq!(WordBuilder::new().with_text(&text).call(), "hello");
+    assert_eq!(WordBuilder::new().with_text(&text).with_index(1).call(), "world");
+    assert_eq!(LabelBuilder::new().with_name("a".into()).with_suffix("!").call(), "a!");
+}
@@ -0,0 +1,38 @@
+#[derive(PartialEq, Debug)]
+pub struct Server<T> {
+    name: String,
+    workers: usize,
+    state: T,
+}
+
+#[jbe::builder]
+impl<T> Server<T> {
+    #[builder]
+    pub fn new(#[builder({into: true})] name: String, state: T) -> Self {
+        Self { name, workers: 4, state }
+    }
+
+    #[builder({typestate: true})]
+    pub fn with_workers(name: String, workers: usize, state: T) -> Self {
+        Self { name, workers, state }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn main() {
+    let server = Server::builder().with_name("a").with_state(1).call();
+    assert_eq!(server, Server { name: String::from("a"), workers: 4, state: 1 });
+    assert_eq!(server.name(), "a");
+    let error = ServerBuilder::<u8>::new().try_call();
+    assert_eq!(error, Err(ServerBuilderError::UnsetName));
+
+    let server = Server::with_workers_builder()
+        .with_workers(2)
+        .with_state(())
+        .with_name(String::from("b"))
+        .call();
+    assert_eq!(server, Server { name: String::from("b"), workers: 2, state: () });
+}
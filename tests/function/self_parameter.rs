@@ -0,0 +1,13 @@
+pub struct Counter {
+    count: usize,
+}
+
+#[jbe::builder]
+impl Counter {
+    #[builder]
+    pub fn add(&mut self, value: usize) {
+        self.count += value;
+    }
+}
+
+fn main() {}
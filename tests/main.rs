@@ -9,4 +9,71 @@ fn derive_builder() {
     t.pass("tests/derive_builder/generic.rs");
     t.pass("tests/derive_builder/generic_where.rs");
     t.pass("tests/derive_builder/generic_default.rs");
+    t.pass("tests/derive_builder/copy_template.rs");
+    t.pass("tests/derive_builder/collect_missing.rs");
+    t.pass("tests/derive_builder/validate.rs");
+    t.pass("tests/derive_builder/validate_struct.rs");
+    t.pass("tests/derive_builder/setter_into.rs");
+    t.pass("tests/derive_builder/option_setter.rs");
+    t.pass("tests/derive_builder/each.rs");
+    t.pass("tests/derive_builder/skip.rs");
+    t.pass("tests/derive_builder/setter_names.rs");
+    t.pass("tests/derive_builder/constructor.rs");
+    t.pass("tests/derive_builder/required_arg.rs");
+    t.pass("tests/derive_builder/lifetime.rs");
+    t.pass("tests/derive_builder/tuple.rs");
+    t.pass("tests/derive_builder/enum.rs");
+    t.pass("tests/derive_builder/env.rs");
+    t.pass("tests/derive_builder/set_by_name.rs");
+    t.pass("tests/derive_builder/reflection.rs");
+    t.pass("tests/derive_builder/accessors.rs");
+    t.pass("tests/derive_builder/conversion.rs");
+    t.pass("tests/derive_builder/merge.rs");
+    t.compile_fail("tests/derive_builder_fail/skip.rs");
+    t.compile_fail("tests/derive_builder_fail/setter_names.rs");
+    t.compile_fail("tests/derive_builder_fail/required_arg.rs");
+    t.compile_fail("tests/derive_builder_fail/tuple.rs");
+    t.compile_fail("tests/derive_builder_fail/enum.rs");
+    t.compile_fail("tests/derive_builder_fail/env.rs");
+}
+
+#[test]
+fn derive_consuming_builder() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/consuming_builder/basic.rs");
+    t.pass("tests/consuming_builder/generic.rs");
+}
+
+#[test]
+fn derive_typestate_builder() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/typestate/basic.rs");
+    t.pass("tests/typestate/generic.rs");
+    t.pass("tests/typestate/lifetime.rs");
+    t.pass("tests/typestate/tuple.rs");
+    t.compile_fail("tests/typestate/missing_field.rs");
+}
+
+#[test]
+fn builder_function() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/function/basic.rs");
+    t.pass("tests/function/method.rs");
+    t.compile_fail("tests/function/self_parameter.rs");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/serde/builder.rs");
+    t.pass("tests/serde/deserialize.rs");
+    t.compile_fail("tests/serde/deserialize_required_arg.rs");
+}
+
+#[cfg(not(feature = "serde"))]
+#[test]
+fn serde_disabled() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/derive_builder_fail/serde.rs");
 }
@@ -0,0 +1,50 @@
+use jbe::Builder;
+use serde::{Deserialize, Serialize};
+
+#[derive(Builder, Serialize, Deserialize, PartialEq, Debug)]
+#[builder({serde: true})]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    #[serde(rename = "workerCount", alias = "workers")]
+    worker_count: usize,
+    log_file: Option<String>,
+    #[serde(default = "default_retries", skip_serializing_if = "is_zero")]
+    retries: u8,
+}
+
+fn default_retries() -> u8 {
+    3
+}
+
+fn is_zero(value: &u8) -> bool {
+    *value == 0
+}
+
+fn main() {
+    let config: Config = serde_json::from_str(r#"{"host": "localhost", "port": 1, "workers": 2}"#).unwrap();
+    assert_eq!(config.retries, 3);
+
+    let builder: ConfigBuilder = serde_json::from_str(r#"{"host": "localhost", "retries": 0}"#).unwrap();
+    let error = builder.try_build();
+    assert_eq!(error, Err(ConfigBuilderError::UnsetWorkerCount));
+
+    let builder: ConfigBuilder =
+        serde_json::from_str(r#"{"host": "localhost", "workers": 2, "logFile": "log.txt", "retries": 5}"#).unwrap();
+    let config = builder.build();
+    assert_eq!(config, Config {
+        host: String::from("localhost"),
+        port: 80,
+        worker_count: 2,
+        log_file: Some(String::from("log.txt")),
+        retries: 5,
+    });
+
+    let builder = ConfigBuilder::new().with_host(String::from("localhost")).with_worker_count(1).with_retries(0);
+    assert_eq!(
+        serde_json::to_string(&builder).unwrap(),
+        r#"{"host":"localhost","workerCount":1,"retries":0}"#
+    );
+}
@@ -0,0 +1,42 @@
+use jbe::Builder;
+
+fn positive(value: &i64) -> Result<(), String> {
+    if *value > 0 {
+        Ok(())
+    } else {
+        Err(String::from("not positive"))
+    }
+}
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({deserialize: true})]
+pub struct Config<T> {
+    host: String,
+    #[builder({default: 80})]
+    port: u16,
+    #[builder({validate: positive})]
+    #[serde(rename = "timeoutSeconds")]
+    timeout: i64,
+    tags: Option<Vec<T>>,
+}
+
+fn main() {
+    let config: Config<String> =
+        serde_json::from_str(r#"{"host": "localhost", "timeoutSeconds": 5}"#).unwrap();
+    assert_eq!(config, Config {
+        host: String::from("localhost"),
+        port: 80,
+        timeout: 5,
+        tags: None,
+    });
+
+    let error = serde_json::from_str::<Config<String>>(r#"{"timeoutSeconds": 5}"#).unwrap_err();
+    assert_eq!(error.to_string(), "Error host not set");
+
+    let error = serde_json::from_str::<Config<String>>(r#"{"host": "localhost", "timeoutSeconds": 0}"#)
+        .unwrap_err();
+    assert_eq!(error.to_string(), "Error timeout invalid: not positive");
+
+    let builder = ConfigBuilder::<u8>::new().with_port(1);
+    assert_eq!(serde_json::to_string(&builder).unwrap(), r#"{"port":1}"#);
+}
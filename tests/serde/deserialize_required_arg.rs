@@ -0,0 +1,11 @@
+use jbe::Builder;
+
+#[derive(Builder)]
+#[builder({deserialize: true})]
+pub struct Config {
+    #[builder({required_arg: true})]
+    host: String,
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct User {
+    id: usize,
+    #[builder({into: true})]
+    name: String,
+    #[builder({
+        default: String::from("empty")
+    })]
+    email: String,
+    nickname: Option<String>
+}
+
+fn main() {
+    let user = UserBuilder::default()
+        .with_nickname(String::from("J"))
+        .with_name(String::from("Jon"))
+        .with_id(10)
+        .build();
+    assert_eq!(user, User {
+        id: 10,
+        name: String::from("Jon"),
+        email: String::from("empty"),
+        nickname: Some(String::from("J"))
+    });
+
+    let builder: UserBuilder = UserBuilder::default();
+    let mut builder = builder.with_id(1).with_id(2).with_name("Jon");
+    builder.set_email(String::from("jon@example.com"));
+    assert_eq!(builder.build(), User {
+        id: 2,
+        name: String::from("Jon"),
+        email: String::from("jon@example.com"),
+        nickname: None
+    });
+}
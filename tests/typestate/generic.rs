@@ -0,0 +1,27 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true, copy: true})]
+pub struct Data<T, U = String>
+    where T: Clone, U: Clone
+{
+    i: usize,
+    data: T,
+    other: Option<U>
+}
+
+fn main() {
+    let template = DataBuilder::default().with_i(1).with_data(5u8);
+    let first: Data<u8> = template.build();
+    let second: Data<u8> = template.with_other(String::from("other")).build();
+    assert_eq!(first, Data {
+        i: 1,
+        data: 5,
+        other: None
+    });
+    assert_eq!(second, Data {
+        i: 1,
+        data: 5,
+        other: Some(String::from("other"))
+    });
+}
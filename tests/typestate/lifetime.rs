@@ -0,0 +1,15 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct Frame<'a, 'b: 'a, const N: usize = 2> {
+    data: &'a [u8; N],
+    name: &'b str,
+    id: Option<usize>
+}
+
+fn main() {
+    let data = [1, 2];
+    let frame: Frame = FrameBuilder::new().with_id(1).with_name("frame").with_data(&data).build();
+    assert_eq!(frame, Frame { data: &data, name: "frame", id: Some(1) });
+}
@@ -0,0 +1,12 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct User {
+    id: usize,
+    name: String
+}
+
+fn main() {
+    let _user = UserBuilder::default().with_id(10).build();
+}
@@ -0,0 +1,18 @@
+use jbe::Builder;
+
+#[derive(Builder, PartialEq, Debug)]
+#[builder({typestate: true})]
+pub struct Point(
+    #[builder({name: x})]
+    i32,
+    #[builder({name: y})]
+    i32,
+    Option<i32>,
+);
+
+fn main() {
+    let point = PointBuilder::new().with_y(2).with_x(1).build();
+    assert_eq!(point, Point(1, 2, None));
+    let point = PointBuilder::new().with_field2(3).with_x(1).with_y(2).build();
+    assert_eq!(point, Point(1, 2, Some(3)));
+}